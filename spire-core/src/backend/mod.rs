@@ -0,0 +1,157 @@
+//! Backend abstraction: factories handing out per-request clients.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::context::{Body, Request, Response};
+use crate::{Error, ErrorKind, Result};
+
+pub mod utils;
+
+/// A per-request client able to resolve a [`Request`] into a [`Response`].
+///
+/// Clients are acquired from a [`Backend`] for the duration of a single
+/// request and released (usually back into a pool) when dropped.
+#[async_trait]
+pub trait Client: Send + 'static {
+    /// Resolves a single request into a response.
+    async fn resolve(&mut self, request: Request) -> Result<Response>;
+
+    /// Returns `true` if the client answers `HEAD` requests cheaply.
+    ///
+    /// Clients without a lightweight `HEAD` path — a browser session
+    /// navigates regardless of the method — return `false`, turning the
+    /// preflight of [`Client::with_head_preflight`] into a no-op.
+    ///
+    /// [`Client::with_head_preflight`]: crate::client::Client::with_head_preflight
+    fn supports_head(&self) -> bool {
+        true
+    }
+}
+
+/// A factory handing out [`Client`]s, typically backed by a connection pool.
+#[async_trait]
+pub trait Backend: Clone + Send + Sync + 'static {
+    /// The client type produced by this backend.
+    type Client: Client;
+
+    /// Acquires a client for processing a single request.
+    async fn client(&self) -> Result<Self::Client>;
+
+    /// Acquires a client, failing with [`ErrorKind::Timeout`] once the
+    /// bound elapses.
+    ///
+    /// [`Backend::client`] is allowed to block indefinitely — an
+    /// exhausted pool waiting for a returned session is the common case —
+    /// so the runner calls this instead when a timeout is configured via
+    /// [`Client::with_client_timeout`], keeping a stuck backend from
+    /// freezing the whole crawl.
+    ///
+    /// [`Client::with_client_timeout`]: crate::client::Client::with_client_timeout
+    async fn client_timeout(&self, timeout: Duration) -> Result<Self::Client> {
+        match tokio::time::timeout(timeout, self.client()).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::msg(
+                ErrorKind::Timeout,
+                format!("client acquisition exceeded {timeout:?}"),
+            )),
+        }
+    }
+}
+
+/// The boxed future produced by a [`Fetcher`] call.
+type FetchFuture = Pin<Box<dyn Future<Output = Result<Response>> + Send>>;
+
+/// Fetches auxiliary resources through the backend a crawl runs on.
+///
+/// Extractable in handlers, letting them resolve additional URLs without
+/// round-tripping through the request queue. Every call acquires its own
+/// client from the backend, so with pooled backends a fetch from inside a
+/// handler holds a second session while the handler's own session is still
+/// checked out: size the pool for that, or configure an acquire strategy
+/// that fails fast rather than waiting on an exhausted pool. The browser
+/// backend navigates that separately acquired session; it does not reuse
+/// the page the handler is currently on.
+#[derive(Clone)]
+pub struct Fetcher {
+    inner: Arc<dyn Fn(Request) -> FetchFuture + Send + Sync>,
+}
+
+impl Fetcher {
+    /// Creates a fetcher acquiring clients from the given backend.
+    pub fn new<B: Backend>(backend: B) -> Self {
+        let inner = Arc::new(move |request: Request| -> FetchFuture {
+            let backend = backend.clone();
+            Box::pin(async move { backend.client().await?.resolve(request).await })
+        });
+
+        Self { inner }
+    }
+
+    /// Resolves a `GET` request for the given URI.
+    pub async fn fetch(&self, uri: impl AsRef<str>) -> Result<Response> {
+        let request = http::Request::get(uri.as_ref())
+            .body(Body::empty())
+            .map_err(|error| Error::new(ErrorKind::Http, error))?;
+        self.fetch_request(request).await
+    }
+
+    /// Resolves an arbitrary request.
+    pub async fn fetch_request(&self, request: Request) -> Result<Response> {
+        (self.inner)(request).await
+    }
+}
+
+impl fmt::Debug for Fetcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fetcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::utils::Noop;
+
+    #[tokio::test]
+    async fn fetches_through_backend() {
+        let fetcher = Fetcher::new(Noop::new());
+        let response = fetcher.fetch("https://example.com/api").await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquisition_timeout_fails_instead_of_hanging() {
+        /// Backend whose acquisition never completes, like an exhausted
+        /// pool with no sessions coming back.
+        #[derive(Debug, Clone)]
+        struct Stuck;
+
+        #[async_trait]
+        impl Backend for Stuck {
+            type Client = Noop;
+
+            async fn client(&self) -> Result<Self::Client> {
+                std::future::pending().await
+            }
+        }
+
+        let error = Stuck
+            .client_timeout(Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Timeout);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_uris() {
+        let fetcher = Fetcher::new(Noop::new());
+        let error = fetcher.fetch("not a uri").await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Http);
+    }
+}
@@ -0,0 +1,526 @@
+//! Backend utilities for tests and local development.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use http::{HeaderMap, StatusCode};
+
+use crate::backend::{Backend, Client};
+use crate::context::{Body, Request, Response};
+use crate::{Error, ErrorKind, Result};
+
+/// Backend whose clients answer every request with an empty `200 OK`.
+///
+/// Useful for exercising routing, middleware and dataset plumbing without
+/// touching the network.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Noop;
+
+impl Noop {
+    /// Creates a new no-op backend.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Client for Noop {
+    async fn resolve(&mut self, _request: Request) -> Result<Response> {
+        let response = http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(Body::empty())
+            .expect("empty response should always build");
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Backend for Noop {
+    type Client = Noop;
+
+    async fn client(&self) -> Result<Self::Client> {
+        Ok(*self)
+    }
+}
+
+/// Stored response parts, rebuilt into a fresh [`Response`] per request.
+#[derive(Debug, Clone)]
+struct CannedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Body,
+}
+
+impl CannedResponse {
+    fn into_response(self) -> Response {
+        let mut response = http::Response::new(self.body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+/// Backend whose clients answer requests with pre-registered responses.
+///
+/// Responses are keyed by the full request URL; requests without a match
+/// receive an empty `404 Not Found`. Useful for testing extraction logic
+/// deterministically without a live server:
+///
+/// ```rust
+/// use spire_core::backend::utils::MockBackend;
+/// use spire_core::context::Body;
+///
+/// let response = http::Response::builder()
+///     .status(http::StatusCode::OK)
+///     .body(Body::new("<html>hello</html>"))
+///     .unwrap();
+///
+/// let backend = MockBackend::new().expect("https://example.com/", response);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockBackend {
+    responses: HashMap<String, CannedResponse>,
+}
+
+impl MockBackend {
+    /// Creates a backend with no registered responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a response for requests to the given URL.
+    pub fn expect(mut self, url: impl Into<String>, response: Response) -> Self {
+        let (parts, body) = response.into_parts();
+        let canned = CannedResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        };
+
+        self.responses.insert(url.into(), canned);
+        self
+    }
+}
+
+#[async_trait]
+impl Client for MockBackend {
+    async fn resolve(&mut self, request: Request) -> Result<Response> {
+        let url = request.uri().to_string();
+        match self.responses.get(&url) {
+            Some(canned) => Ok(canned.clone().into_response()),
+            None => {
+                let response = http::Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::empty())
+                    .expect("empty response should always build");
+                Ok(response)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for MockBackend {
+    type Client = MockBackend;
+
+    async fn client(&self) -> Result<Self::Client> {
+        Ok(self.clone())
+    }
+}
+
+/// Backend serving responses from files in a local directory.
+///
+/// Request paths map straight onto the directory: `/products/1.html`
+/// resolves to `<root>/products/1.html`, and `/` (or any path ending in
+/// a slash) to the `index.html` inside. Content types are inferred from
+/// the file extension. Missing files answer with an empty `404`.
+///
+/// Useful for benchmarking the runner and router without network
+/// overhead, or for reproducing a saved copy of a site locally:
+///
+/// ```rust
+/// use spire_core::backend::utils::FixtureBackend;
+///
+/// let backend = FixtureBackend::new("fixtures/");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixtureBackend {
+    root: PathBuf,
+}
+
+impl FixtureBackend {
+    /// Creates a backend serving files under the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Maps a request path onto a file under the root, refusing paths
+    /// that would escape it.
+    fn file_for(&self, path: &str) -> Option<PathBuf> {
+        let trimmed = path.trim_start_matches('/');
+        let relative = match trimmed {
+            "" => "index.html",
+            _ if trimmed.ends_with('/') => return Some(self.root.join(trimmed).join("index.html")),
+            _ => trimmed,
+        };
+
+        let escapes = std::path::Path::new(relative)
+            .components()
+            .any(|component| matches!(component, std::path::Component::ParentDir));
+        (!escapes).then(|| self.root.join(relative))
+    }
+
+    /// Infers a content type from the file extension.
+    fn content_type(path: &std::path::Path) -> &'static str {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("html" | "htm") => "text/html; charset=utf-8",
+            Some("json") => "application/json",
+            Some("txt") => "text/plain; charset=utf-8",
+            Some("xml") => "application/xml",
+            Some("css") => "text/css",
+            Some("js") => "text/javascript",
+            Some("png") => "image/png",
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("svg") => "image/svg+xml",
+            _ => "application/octet-stream",
+        }
+    }
+
+    fn not_found() -> Response {
+        http::Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("empty response should always build")
+    }
+}
+
+#[async_trait]
+impl Client for FixtureBackend {
+    async fn resolve(&mut self, request: Request) -> Result<Response> {
+        let Some(path) = self.file_for(request.uri().path()) else {
+            tracing::debug!(uri = %request.uri(), "fixture path escapes the root");
+            return Ok(Self::not_found());
+        };
+
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::not_found());
+            }
+            Err(error) => return Err(Error::new(ErrorKind::Backend, error)),
+        };
+
+        let response = http::Response::builder()
+            .status(StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, Self::content_type(&path))
+            .body(Body::new(bytes))
+            .expect("static response parts should build");
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Backend for FixtureBackend {
+    type Client = FixtureBackend;
+
+    async fn client(&self) -> Result<Self::Client> {
+        Ok(self.clone())
+    }
+}
+
+/// How a [`Cassette`] treats its tape file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Always resolve through the inner backend and record the response.
+    Record,
+    /// Always replay from the tape; unrecorded requests fail.
+    Replay,
+    /// Replay when the tape has a matching entry, record otherwise.
+    Auto,
+}
+
+/// Record-and-replay wrapper around another [`Backend`].
+///
+/// On first run the inner backend resolves requests normally and every
+/// response is written to a JSON tape file, keyed by method and URL. On
+/// later runs the tape replays those responses without touching the
+/// network, making integration tests against real sites reproducible.
+///
+/// The tape stores status, headers and body; bodies are stored as UTF-8
+/// text, so binary payloads do not round-trip. Response extensions are
+/// not recorded.
+#[derive(Debug, Clone)]
+pub struct Cassette<B> {
+    inner: B,
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl<B> Cassette<B> {
+    /// Wraps a backend with a tape at the given path.
+    pub fn new(inner: B, path: impl Into<PathBuf>, mode: Mode) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            mode,
+        }
+    }
+}
+
+#[async_trait]
+impl<B> Backend for Cassette<B>
+where
+    B: Backend,
+{
+    type Client = CassetteClient<B::Client>;
+
+    async fn client(&self) -> Result<Self::Client> {
+        Ok(CassetteClient {
+            inner: self.inner.client().await?,
+            path: self.path.clone(),
+            mode: self.mode,
+        })
+    }
+}
+
+/// Client produced by a [`Cassette`] backend.
+#[derive(Debug)]
+pub struct CassetteClient<C> {
+    inner: C,
+    path: PathBuf,
+    mode: Mode,
+}
+
+#[async_trait]
+impl<C> Client for CassetteClient<C>
+where
+    C: Client,
+{
+    async fn resolve(&mut self, request: Request) -> Result<Response> {
+        let key = format!("{} {}", request.method(), request.uri());
+
+        if matches!(self.mode, Mode::Replay | Mode::Auto) {
+            let tape = tape::load(&self.path)?;
+            if let Some(entry) = tape.get(&key) {
+                return tape::replay(entry);
+            }
+            if self.mode == Mode::Replay {
+                let reason = format!("no recorded response for `{key}`");
+                return Err(Error::msg(ErrorKind::Backend, reason));
+            }
+        }
+
+        let response = self.inner.resolve(request).await?;
+
+        let mut tape = tape::load(&self.path)?;
+        tape.insert(key, tape::record(&response));
+        tape::save(&self.path, &tape)?;
+
+        Ok(response)
+    }
+}
+
+mod tape {
+    //! JSON (de)serialization of the [`Cassette`] tape file.
+    //!
+    //! [`Cassette`]: super::Cassette
+
+    use std::path::Path;
+
+    use http::StatusCode;
+    use serde_json::{json, Map, Value};
+
+    use crate::context::{Body, Response};
+    use crate::{Error, ErrorKind, Result};
+
+    /// Reads the tape, treating a missing file as an empty tape.
+    pub(super) fn load(path: &Path) -> Result<Map<String, Value>> {
+        let raw = match std::fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Map::new());
+            }
+            Err(error) => return Err(Error::new(ErrorKind::Backend, error)),
+        };
+
+        let value: Value =
+            serde_json::from_str(&raw).map_err(|error| Error::new(ErrorKind::Backend, error))?;
+        match value {
+            Value::Object(map) => Ok(map),
+            _ => {
+                let reason = format!("malformed cassette tape at {}", path.display());
+                Err(Error::msg(ErrorKind::Backend, reason))
+            }
+        }
+    }
+
+    /// Writes the tape back to disk.
+    pub(super) fn save(path: &Path, tape: &Map<String, Value>) -> Result<()> {
+        let raw = serde_json::to_string_pretty(tape)
+            .map_err(|error| Error::new(ErrorKind::Backend, error))?;
+        std::fs::write(path, raw).map_err(|error| Error::new(ErrorKind::Backend, error))
+    }
+
+    /// Serializes a response into a tape entry.
+    pub(super) fn record(response: &Response) -> Value {
+        let mut headers = Map::new();
+        for (name, value) in response.headers() {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            headers.insert(name.to_string(), Value::String(value));
+        }
+
+        json!({
+            "status": response.status().as_u16(),
+            "headers": headers,
+            "body": String::from_utf8_lossy(response.body().bytes()).into_owned(),
+        })
+    }
+
+    /// Rebuilds a response from a tape entry.
+    pub(super) fn replay(entry: &Value) -> Result<Response> {
+        let malformed = || Error::msg(ErrorKind::Backend, "malformed cassette entry");
+
+        let status = entry.get("status").and_then(Value::as_u64).ok_or_else(malformed)?;
+        let status = StatusCode::from_u16(status as u16).map_err(|_| malformed())?;
+        let body = entry.get("body").and_then(Value::as_str).ok_or_else(malformed)?;
+
+        let mut response = http::Response::new(Body::new(body.to_owned()));
+        *response.status_mut() = status;
+
+        if let Some(headers) = entry.get("headers").and_then(Value::as_object) {
+            for (name, value) in headers {
+                let name: http::HeaderName = name.parse().map_err(|_| malformed())?;
+                let value = value.as_str().ok_or_else(malformed)?;
+                let value = value.parse().map_err(|_| malformed())?;
+                response.headers_mut().insert(name, value);
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use http::StatusCode;
+
+    use crate::backend::utils::{Cassette, FixtureBackend, MockBackend, Mode};
+    use crate::backend::{Backend, Client};
+    use crate::context::{Body, Request};
+
+    fn request(url: &str) -> Request {
+        http::Request::get(url)
+            .body(Body::empty())
+            .expect("request should build")
+    }
+
+    #[tokio::test]
+    async fn replays_registered_responses() {
+        let canned = http::Response::builder()
+            .status(StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, "text/html")
+            .body(Body::new("<html>hello</html>"))
+            .unwrap();
+
+        let backend = MockBackend::new().expect("https://example.com/", canned);
+        let mut client = backend.client().await.unwrap();
+
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[http::header::CONTENT_TYPE], "text/html");
+        assert_eq!(response.into_body().into_bytes(), "<html>hello</html>");
+
+        // Canned responses replay on every matching request.
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn unmatched_requests_get_not_found() {
+        let backend = MockBackend::new();
+        let mut client = backend.client().await.unwrap();
+
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn fixture_serves_files_with_inferred_types() {
+        let root = std::env::temp_dir().join(format!("spire-fixtures-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("index.html"), "<html>home</html>").unwrap();
+        std::fs::write(root.join("data.json"), "{}").unwrap();
+
+        let backend = FixtureBackend::new(&root);
+        let mut client = backend.client().await.unwrap();
+
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.into_body().into_bytes(), "<html>home</html>");
+
+        let response = client.resolve(request("https://example.com/data.json")).await.unwrap();
+        assert_eq!(response.headers()[http::header::CONTENT_TYPE], "application/json");
+
+        let response = client.resolve(request("https://example.com/missing.html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = client
+            .resolve(request("https://example.com/../secret.txt"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    fn tape_path(name: &str) -> std::path::PathBuf {
+        let file = format!("spire-cassette-{name}-{}.json", std::process::id());
+        std::env::temp_dir().join(file)
+    }
+
+    #[tokio::test]
+    async fn cassette_records_then_replays() {
+        let path = tape_path("roundtrip");
+        let _ = std::fs::remove_file(&path);
+
+        let canned = http::Response::builder()
+            .status(StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, "text/html")
+            .body(Body::new("<html>hello</html>"))
+            .unwrap();
+        let inner = MockBackend::new().expect("https://example.com/", canned);
+
+        let recorder = Cassette::new(inner, &path, Mode::Record);
+        let mut client = recorder.client().await.unwrap();
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Replaying against an empty inner backend proves the tape is used.
+        let replayer = Cassette::new(MockBackend::new(), &path, Mode::Replay);
+        let mut client = replayer.client().await.unwrap();
+        let response = client.resolve(request("https://example.com/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[http::header::CONTENT_TYPE], "text/html");
+        assert_eq!(response.into_body().into_bytes(), "<html>hello</html>");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn cassette_replay_rejects_unrecorded_requests() {
+        let path = tape_path("strict");
+        let _ = std::fs::remove_file(&path);
+
+        let backend = Cassette::new(MockBackend::new(), &path, Mode::Replay);
+        let mut client = backend.client().await.unwrap();
+
+        let error = client.resolve(request("https://example.com/")).await.unwrap_err();
+        assert_eq!(error.kind(), crate::ErrorKind::Backend);
+    }
+}
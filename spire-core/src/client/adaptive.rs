@@ -0,0 +1,127 @@
+use std::sync::Mutex;
+
+use crate::signal::Signal;
+
+/// AIMD controller deciding the runner's concurrency limit from recent
+/// request outcomes.
+///
+/// Additive increase, multiplicative decrease: one full window of healthy
+/// outcomes raises the limit by one, a single unhealthy outcome halves it.
+/// [`Signal::Fail`] and [`Signal::Wait`] both count as unhealthy — a
+/// `Wait` is the server asking the crawl to back off, which is exactly
+/// when pushing more concurrent requests hurts.
+#[derive(Debug)]
+pub(crate) struct AdaptiveConcurrency {
+    min: usize,
+    max: usize,
+    state: Mutex<AdaptiveState>,
+}
+
+#[derive(Debug)]
+struct AdaptiveState {
+    limit: usize,
+    /// Healthy outcomes since the last adjustment; a streak of `limit`
+    /// of them earns one more permit.
+    streak: usize,
+}
+
+impl AdaptiveConcurrency {
+    /// Creates a controller bounded to `min..=max` permits, starting at
+    /// `min`.
+    pub(crate) fn new(min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        let max = max.max(min);
+
+        Self {
+            min,
+            max,
+            state: Mutex::new(AdaptiveState {
+                limit: min,
+                streak: 0,
+            }),
+        }
+    }
+
+    /// Returns the current target limit.
+    pub(crate) fn target(&self) -> usize {
+        self.state.lock().expect("adaptive lock poisoned").limit
+    }
+
+    /// Records a request outcome, adjusting the target limit.
+    pub(crate) fn record(&self, signal: &Signal) {
+        let mut state = self.state.lock().expect("adaptive lock poisoned");
+        match signal {
+            Signal::Continue | Signal::Skip | Signal::Stop => {
+                state.streak += 1;
+                if state.streak >= state.limit {
+                    state.streak = 0;
+                    state.limit = (state.limit + 1).min(self.max);
+                }
+            }
+            Signal::Wait(_) | Signal::Fail(_) => {
+                state.streak = 0;
+                state.limit = (state.limit / 2).max(self.min);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Error, ErrorKind};
+
+    fn fail() -> Signal {
+        Signal::Fail(Error::msg(ErrorKind::Http, "boom"))
+    }
+
+    #[test]
+    fn window_of_successes_adds_one_permit() {
+        let adaptive = AdaptiveConcurrency::new(2, 8);
+        assert_eq!(adaptive.target(), 2);
+
+        adaptive.record(&Signal::Continue);
+        assert_eq!(adaptive.target(), 2);
+        adaptive.record(&Signal::Skip);
+        assert_eq!(adaptive.target(), 3);
+    }
+
+    #[test]
+    fn failures_halve_the_limit() {
+        let adaptive = AdaptiveConcurrency::new(1, 16);
+        for _ in 0..50 {
+            adaptive.record(&Signal::Continue);
+        }
+        let grown = adaptive.target();
+        assert!(grown > 4);
+
+        adaptive.record(&fail());
+        assert_eq!(adaptive.target(), grown / 2);
+    }
+
+    #[test]
+    fn limit_stays_within_bounds() {
+        let adaptive = AdaptiveConcurrency::new(2, 4);
+        for _ in 0..100 {
+            adaptive.record(&Signal::Continue);
+        }
+        assert_eq!(adaptive.target(), 4);
+
+        for _ in 0..10 {
+            adaptive.record(&fail());
+        }
+        assert_eq!(adaptive.target(), 2);
+    }
+
+    #[test]
+    fn wait_counts_as_back_off() {
+        let adaptive = AdaptiveConcurrency::new(1, 8);
+        for _ in 0..20 {
+            adaptive.record(&Signal::Continue);
+        }
+        let grown = adaptive.target();
+
+        adaptive.record(&Signal::Wait(std::time::Duration::from_secs(1)));
+        assert_eq!(adaptive.target(), grown / 2);
+    }
+}
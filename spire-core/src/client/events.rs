@@ -0,0 +1,154 @@
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use futures_core::Stream;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use http::Uri;
+use tokio::sync::broadcast;
+
+use crate::context::Tag;
+use crate::signal::Signal;
+
+/// Capacity of the broadcast channel behind [`EventStream`].
+///
+/// A consumer falling further behind than this loses the oldest events;
+/// the crawl itself is never slowed down.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A structured event emitted during a crawl.
+///
+/// Subscribed to via [`Client::events`]; meant for forwarding crawl
+/// progress into external systems — message queues, websockets,
+/// dashboards — without scraping logs.
+///
+/// [`Client::events`]: crate::client::Client::events
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum CrawlEvent {
+    /// A follow-up request was appended to the queue.
+    RequestEnqueued {
+        /// The routing tag of the enqueued request.
+        tag: Tag,
+        /// The URI of the enqueued request.
+        uri: Uri,
+    },
+    /// A dequeued task was dispatched to its handler.
+    RequestStarted {
+        /// The routing tag of the dispatched request.
+        tag: Tag,
+        /// The URI of the dispatched request.
+        uri: Uri,
+    },
+    /// A request ran to completion.
+    RequestFinished {
+        /// The routing tag of the finished request.
+        tag: Tag,
+        /// The URI of the finished request.
+        uri: Uri,
+        /// The signal the request resolved to.
+        outcome: CrawlOutcome,
+    },
+}
+
+/// The resolution attached to [`CrawlEvent::RequestFinished`].
+///
+/// A clonable mirror of [`Signal`], with the failure reduced to its
+/// message so events stay cheap to copy between consumers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CrawlOutcome {
+    /// The request completed normally.
+    Continue,
+    /// The request was skipped.
+    Skip,
+    /// The request asked the crawl to back off.
+    Wait,
+    /// The request failed with the contained error message.
+    Fail(String),
+    /// The request asked the crawl to finish gracefully.
+    Stop,
+}
+
+impl From<&Signal> for CrawlOutcome {
+    fn from(signal: &Signal) -> Self {
+        match signal {
+            Signal::Continue => CrawlOutcome::Continue,
+            Signal::Skip => CrawlOutcome::Skip,
+            Signal::Wait(_) => CrawlOutcome::Wait,
+            Signal::Fail(error) => CrawlOutcome::Fail(error.to_string()),
+            Signal::Stop => CrawlOutcome::Stop,
+        }
+    }
+}
+
+/// Shared sender side of the crawl event channel.
+///
+/// Publishing is a single non-blocking broadcast send, and the event is
+/// not even constructed while nobody subscribes, so the publisher can sit
+/// on the hot dispatch path.
+#[derive(Debug, Clone)]
+pub(crate) struct EventPublisher {
+    sender: broadcast::Sender<CrawlEvent>,
+}
+
+impl EventPublisher {
+    /// Publishes the event built by the given closure to all subscribers.
+    pub(crate) fn publish(&self, event: impl FnOnce() -> CrawlEvent) {
+        if self.sender.receiver_count() > 0 {
+            let _ = self.sender.send(event());
+        }
+    }
+
+    /// Opens a new subscription receiving events from this point on.
+    pub(crate) fn subscribe(&self) -> EventStream {
+        let receiver = self.sender.subscribe();
+        let inner = stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "crawl event consumer lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        EventStream {
+            inner: inner.boxed(),
+        }
+    }
+}
+
+impl Default for EventPublisher {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+}
+
+/// Stream of [`CrawlEvent`]s, returned by [`Client::events`].
+///
+/// Ends once the crawl is over and every buffered event was consumed. A
+/// subscriber slower than the crawl loses the oldest events rather than
+/// slowing the crawl down.
+///
+/// [`Client::events`]: crate::client::Client::events
+#[must_use = "streams do nothing unless polled"]
+pub struct EventStream {
+    inner: BoxStream<'static, CrawlEvent>,
+}
+
+impl Stream for EventStream {
+    type Item = CrawlEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,933 @@
+//! Crawler entry point wiring a backend, a router and datasets together.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::Uri;
+
+use crate::backend::Backend;
+use crate::context::{Body, LoopGuard, QueueHooks, Response, Tag, Task};
+use crate::dataset::{Data, DataSink, DataStream, Dataset, DatasetRegistry, InMemDataset};
+use crate::routing::{Router, Routes};
+use crate::signal::{CancelToken, SignalStats};
+use crate::Result;
+
+mod adaptive;
+pub(crate) mod events;
+mod runner;
+mod throttle;
+
+use adaptive::AdaptiveConcurrency;
+pub use events::{CrawlEvent, CrawlOutcome, EventStream};
+use runner::Runner;
+use throttle::Throttle;
+
+/// The default number of requests processed concurrently.
+const DEFAULT_CONCURRENCY: usize = 16;
+
+/// Shared predicate deciding from a `HEAD` response whether the full
+/// request is worth dispatching; see [`Client::with_head_preflight`].
+pub type HeadPredicate = Arc<dyn Fn(&Response) -> bool + Send + Sync>;
+
+/// The dispatch order of the crawl frontier; see
+/// [`Client::with_traversal`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Traversal {
+    /// Breadth-first: requests dispatch in the order they were enqueued,
+    /// finishing each depth level before descending. The default.
+    #[default]
+    Bfs,
+    /// Depth-first: the most recently enqueued request dispatches first,
+    /// following each branch to its leaves before backtracking.
+    Dfs,
+}
+
+/// A configured crawler: couples a [`Backend`] with a [`Router`] and
+/// drives the run loop.
+///
+/// Requests are stored as [`Task`]s in the queue dataset (an in-memory
+/// FIFO unless replaced via [`Client::with_queue`]) and processed
+/// concurrently up to the configured limit. The crawl finishes once the
+/// queue is observed empty with no requests in flight.
+pub struct Client<B: Backend> {
+    backend: B,
+    routes: Routes<B::Client>,
+    queue: Data<Task>,
+    datasets: DatasetRegistry,
+    seeds: Vec<Result<Task>>,
+    limit: usize,
+    batch: usize,
+    hooks: QueueHooks,
+    stats: SignalStats,
+    throttle: Option<Arc<Throttle>>,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
+    idle: Option<Duration>,
+    preflight: Option<HeadPredicate>,
+    client_timeout: Option<Duration>,
+}
+
+impl<B: Backend> Client<B> {
+    /// Creates a client from a backend and a router.
+    pub fn new<S>(backend: B, router: Router<B::Client, S>) -> Self
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        Self {
+            backend,
+            routes: router.into_routes(),
+            queue: Data::new(InMemDataset::queue()),
+            datasets: DatasetRegistry::new(),
+            seeds: Vec::new(),
+            limit: DEFAULT_CONCURRENCY,
+            batch: 1,
+            hooks: QueueHooks::default(),
+            stats: SignalStats::default(),
+            throttle: None,
+            adaptive: None,
+            idle: None,
+            preflight: None,
+            client_timeout: None,
+        }
+    }
+
+    /// Replaces the dataset backing the request queue.
+    ///
+    /// The read order of the dataset decides the crawl shape: a FIFO
+    /// queue crawls breadth-first, a LIFO stack depth-first.
+    pub fn with_queue<D>(mut self, dataset: D) -> Self
+    where
+        D: Dataset<Task> + Clone,
+    {
+        self.queue = Data::new(dataset);
+        self
+    }
+
+    /// Registers a dataset for the record type `T`.
+    pub fn with_dataset<T, D>(self, dataset: D) -> Self
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+    {
+        self.datasets.set(dataset);
+        self
+    }
+
+    /// Registers a factory constructing the dataset for the record type
+    /// `T` on first access.
+    ///
+    /// Where [`Client::with_dataset`] registers eagerly, the factory
+    /// runs only when a handler first extracts `Data<T>` — useful when
+    /// creating the dataset has a cost (e.g. a database-backed one) and
+    /// not every run touches every record type. The factory is invoked
+    /// exactly once, under the registry lock, so it must not block; see
+    /// [`DatasetRegistry::set_factory`].
+    pub fn with_dataset_factory<T, D, F>(self, factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+        F: Fn() -> D + Send + Sync + 'static,
+    {
+        self.datasets.set_factory(factory);
+        self
+    }
+
+    /// Registers a dataset for the record type `T`, visible only to
+    /// requests routed under the given tag.
+    ///
+    /// Lets conceptually tag-scoped output land in separate stores — a
+    /// `product` handler and an `article` handler can both extract
+    /// `Data<T>` and write to different datasets. Tags without a binding
+    /// fall back to the crawl-wide dataset of [`Client::with_dataset`].
+    pub fn with_dataset_for_tag<T, D>(self, tag: impl Into<Tag>, dataset: D) -> Self
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+    {
+        self.datasets.set_for_tag(tag, dataset);
+        self
+    }
+
+    /// Selects the crawl shape by name instead of by queue constructor.
+    ///
+    /// [`Traversal::Bfs`] stores the frontier in a FIFO
+    /// ([`InMemDataset::queue`]), [`Traversal::Dfs`] in a LIFO
+    /// ([`InMemDataset::stack`]) — this merely spells out which is
+    /// which. Replaces the queue dataset, so combine it with
+    /// [`Client::with_queue`] by picking one or the other; under a
+    /// concurrency limit above `1` the order holds per dispatch, while
+    /// completions still interleave.
+    pub fn with_traversal(mut self, traversal: Traversal) -> Self {
+        self.queue = match traversal {
+            Traversal::Bfs => Data::new(InMemDataset::queue()),
+            Traversal::Dfs => Data::new(InMemDataset::stack()),
+        };
+        self
+    }
+
+    /// Sets the number of requests processed concurrently.
+    ///
+    /// A limit of `1` processes requests strictly in queue order, making
+    /// the crawl deterministic — useful when reproducing a run in tests.
+    /// See the ordering notes on [`InMemDataset`] for what the queue
+    /// datasets themselves guarantee.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Adjusts the concurrency limit within `min..=max` from recent
+    /// request outcomes instead of pinning it.
+    ///
+    /// An AIMD (additive-increase, multiplicative-decrease) controller:
+    /// the limit starts at `min`, grows by one permit per window of
+    /// healthy outcomes and halves whenever a request fails or asks the
+    /// crawl to back off. This finds a workable concurrency level without
+    /// manual tuning; a limit set via [`Client::with_concurrency_limit`]
+    /// is ignored while adaptive concurrency is enabled.
+    pub fn with_adaptive_concurrency(mut self, min: usize, max: usize) -> Self {
+        self.adaptive = Some(Arc::new(AdaptiveConcurrency::new(min, max)));
+        self
+    }
+
+    /// Returns a stream of structured [`CrawlEvent`]s emitted by the run.
+    ///
+    /// Subscribe before calling [`Client::run`] and forward the events
+    /// wherever the surrounding pipeline needs them — a message queue, a
+    /// websocket, a progress bar. Publishing never blocks the crawl: a
+    /// subscriber that falls more than a channel's worth behind loses the
+    /// oldest events instead of slowing dispatch down. The stream ends
+    /// once the run is over and its buffered events are drained.
+    pub fn events(&self) -> EventStream {
+        self.hooks.events.subscribe()
+    }
+
+    /// Splits the request queue into a write half and a read half for
+    /// external tooling.
+    ///
+    /// Both halves implement the `futures` channel traits, so they drop
+    /// into `send_all`, `forward` or a `select!` loop: the sink feeds
+    /// [`Task`]s into a live crawl (pair with [`Client::with_idle_timeout`]
+    /// so a momentarily drained queue keeps waiting for them), the stream
+    /// drains queued tasks for inspection.
+    ///
+    /// Both halves share the queue dataset with the runner, which
+    /// serializes access — using them during a run is safe. Mind the
+    /// semantics though: every task the stream yields was *removed* from
+    /// the queue and will not be dispatched, so during a run it acts as a
+    /// selective drain; for passive observation use [`Client::events`]
+    /// instead. The stream ends at the first empty read, which is not
+    /// necessarily the end of the crawl.
+    pub fn request_queue_split(&self) -> (DataSink<Task>, DataStream<Task>) {
+        (self.queue.sink(), self.queue.stream())
+    }
+
+    /// Keeps the runner alive for up to `idle_timeout` after the queue is
+    /// observed empty, instead of ending the crawl right away.
+    ///
+    /// While idling, the runner wakes as soon as a handler enqueues new
+    /// work and resumes dispatching; only a full `idle_timeout` without
+    /// any append concludes the crawl. Meant for producer/consumer
+    /// topologies where work arrives in bursts — a temporarily drained
+    /// queue no longer ends the run while producers are still going.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle = Some(idle_timeout);
+        self
+    }
+
+    /// Issues a `HEAD` request first and skips the full request unless
+    /// the predicate accepts the preflight response.
+    ///
+    /// The predicate typically inspects the advertised content type and
+    /// length (see [`ResponseExt`]) to avoid downloading huge or
+    /// wrong-type resources on mixed-content sites. A skipped request
+    /// counts as [`Signal::Skip`]; a failing `HEAD` is logged and the
+    /// full request proceeds, since many servers mishandle `HEAD`. Only
+    /// `GET` requests are preflighted, and clients without a cheap
+    /// `HEAD` path (the browser backend) ignore the preflight entirely.
+    ///
+    /// [`ResponseExt`]: crate::context::ResponseExt
+    /// [`Signal::Skip`]: crate::signal::Signal::Skip
+    pub fn with_head_preflight<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Response) -> bool + Send + Sync + 'static,
+    {
+        self.preflight = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Bounds how long acquiring a backend client may take per request.
+    ///
+    /// [`Backend::client`] can block indefinitely — most commonly an
+    /// exhausted pool waiting for a session to come back — which would
+    /// otherwise occupy a concurrency slot forever. With a timeout set,
+    /// such an acquisition fails the request with
+    /// [`ErrorKind::Timeout`](crate::ErrorKind::Timeout) instead of
+    /// freezing the crawl. Applies to every backend, HTTP and browser
+    /// pools alike.
+    pub fn with_client_timeout(mut self, timeout: Duration) -> Self {
+        self.client_timeout = Some(timeout);
+        self
+    }
+
+    /// Caps the dispatch rate at `global_rps` requests per second across
+    /// the crawl and `per_host_rps` per host.
+    ///
+    /// One knob for the common politeness case, installed directly in
+    /// the run loop — no middleware assembly required. A non-positive
+    /// rate leaves that dimension unlimited.
+    ///
+    /// The throttle composes with [`Client::with_concurrency_limit`]: a
+    /// paced request occupies a concurrency slot while it waits for its
+    /// timeslot, so under a strict throttle there is little to gain from
+    /// raising the concurrency limit far beyond `global_rps` times the
+    /// typical request duration.
+    pub fn with_throttle(mut self, global_rps: f64, per_host_rps: f64) -> Self {
+        self.throttle = Some(Arc::new(Throttle::new(global_rps, per_host_rps)));
+        self
+    }
+
+    /// Canonicalizes URLs with the given hook before they are enqueued.
+    ///
+    /// Applied by [`RequestQueue::append`] and
+    /// [`RequestQueue::append_with_tag`], so URL forms that normalize the
+    /// same way collapse into one queue entry. See
+    /// [`normalize_uri`](crate::context::normalize_uri) for a ready-made
+    /// normalizer stripping fragments and sorting query parameters.
+    ///
+    /// [`RequestQueue::append`]: crate::context::RequestQueue::append
+    /// [`RequestQueue::append_with_tag`]: crate::context::RequestQueue::append_with_tag
+    pub fn with_url_normalizer<F>(mut self, normalizer: F) -> Self
+    where
+        F: Fn(Uri) -> Uri + Send + Sync + 'static,
+    {
+        self.hooks.normalizer = Some(Arc::new(normalizer));
+        self
+    }
+
+    /// Sets the `Referer` header of every enqueued follow-up request to
+    /// the URL of the request that enqueued it.
+    ///
+    /// Mimics browser behavior for sites that reject refererless
+    /// requests. A `Referer` already present on the request — e.g. set
+    /// on a prepared [`Task`] — is left untouched, and seeds have no
+    /// source, so they never carry one.
+    pub fn with_auto_referer(mut self, enabled: bool) -> Self {
+        self.hooks.auto_referer = enabled;
+        self
+    }
+
+    /// Breaks crawl loops by capping how often the same URL shape may be
+    /// enqueued.
+    ///
+    /// URLs are fingerprinted by host, path and query parameter *keys*;
+    /// the values — the part that varies endlessly on calendars, session
+    /// ids and similar URL factories — are dropped. Once a fingerprint
+    /// has been enqueued `max_per_fingerprint` times, further appends
+    /// matching it are refused (reported as `Ok(false)`, like the depth
+    /// cap) and a warning is logged the first time. Seeds are not
+    /// counted.
+    pub fn with_loop_guard(mut self, max_per_fingerprint: usize) -> Self {
+        self.hooks.loop_guard = Some(Arc::new(LoopGuard::new(max_per_fingerprint)));
+        self
+    }
+
+    /// Records a `(from, to)` edge in the given dataset whenever a handler
+    /// enqueues a follow-up request.
+    ///
+    /// Edges run from the URI of the enqueueing request to the URI being
+    /// enqueued, yielding a crawl-wide link graph without any handler
+    /// bookkeeping.
+    pub fn with_link_graph<D>(mut self, dataset: D) -> Self
+    where
+        D: Dataset<(Uri, Uri)> + Clone,
+    {
+        self.hooks.link_graph = Some(Data::new(dataset));
+        self
+    }
+
+    /// Invokes the given hook whenever an extractor rejection skips a
+    /// request.
+    ///
+    /// The hook receives the routing tag of the skipped request and the
+    /// rejection reason (e.g. `Json: missing field \`id\``), making it a
+    /// convenient place to count or log pages dropped before their handler
+    /// ran. Rejections are otherwise only visible as debug-level traces.
+    pub fn with_rejection_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Tag, &str) + Send + Sync + 'static,
+    {
+        self.hooks.rejection = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets how many requests are dequeued from the queue dataset at once.
+    ///
+    /// Dispatch still honors the concurrency limit; batching only cuts
+    /// the number of round trips to the queue, which pays off when the
+    /// queue is backed by slow storage (e.g. a database) rather than the
+    /// in-memory default. A batch size of `1` (the default) reads one
+    /// request at a time.
+    pub fn with_batch_size(mut self, batch: usize) -> Self {
+        self.batch = batch.max(1);
+        self
+    }
+
+    /// Sets how many recent request outcomes the [`SignalStats`] window
+    /// holds.
+    ///
+    /// Handlers extract [`SignalStats`] to observe the recent error rate
+    /// and adapt — e.g. stop enqueueing follow-up work while failures
+    /// spike. Defaults to the last 64 outcomes.
+    pub fn with_signal_window(mut self, window: usize) -> Self {
+        self.stats = SignalStats::new(window);
+        self
+    }
+
+    /// Seeds the crawl with an initial request dispatched under the given
+    /// tag.
+    pub fn with_initial_request<T>(mut self, tag: impl Into<Tag>, request: http::Request<T>) -> Self
+    where
+        T: Into<Body>,
+    {
+        let request = request.map(Into::into);
+        self.seeds.push(Ok(Task::new(request).with_tag(tag)));
+        self
+    }
+
+    /// Seeds the crawl with a plain `GET` request to the given URL,
+    /// dispatched under the given tag.
+    ///
+    /// The seed-time mirror of [`RequestQueue::append_with_tag`]; an
+    /// invalid URL surfaces as an error from [`Client::run`].
+    ///
+    /// [`RequestQueue::append_with_tag`]: crate::context::RequestQueue::append_with_tag
+    pub fn with_seed(mut self, tag: impl Into<Tag>, url: impl AsRef<str>) -> Self {
+        self.seeds.push(Task::builder(url).with_tag(tag).build());
+        self
+    }
+
+    /// Seeds the crawl with a tagged URL per element of the iterator.
+    pub fn with_seeds<I, T, U>(mut self, seeds: I) -> Self
+    where
+        I: IntoIterator<Item = (T, U)>,
+        T: Into<Tag>,
+        U: AsRef<str>,
+    {
+        for (tag, url) in seeds {
+            self = self.with_seed(tag, url);
+        }
+
+        self
+    }
+
+    /// Returns a handle to the crawl-wide dataset for the record type
+    /// `T`, registering an in-memory one when none was set.
+    ///
+    /// The handle shares storage with the crawl, so grabbing it before
+    /// [`Client::run`] and reading from it afterwards is the standard
+    /// way to collect results:
+    ///
+    /// ```ignore
+    /// let records = client.dataset::<Product>();
+    /// client.run().await?;
+    /// records.export_json("products.jsonl", ExportFormat::Jsonl).await?;
+    /// ```
+    ///
+    /// See [`Data::export_json`] for the export formats.
+    pub fn dataset<T: Send + Sync + 'static>(&self) -> Data<T> {
+        self.datasets.get_or_default()
+    }
+
+    /// Returns the record type names of the registered datasets, sorted.
+    ///
+    /// Useful when a handler's `Data<T>` comes up unexpectedly empty:
+    /// the culprit is usually a dataset registered under a different
+    /// record type, which this list makes visible.
+    pub fn registered_datasets(&self) -> Vec<&'static str> {
+        self.datasets.names()
+    }
+
+    /// Runs the crawl until the queue is drained.
+    pub async fn run(self) -> Result<()> {
+        self.run_inner(None).await
+    }
+
+    /// Runs the crawl until the queue is drained or `shutdown` resolves.
+    ///
+    /// Once the signal fires no further requests are dispatched; requests
+    /// already in flight are waited out. Handlers extract [`CancelToken`]
+    /// to observe the same signal and cut their own long waits short, so
+    /// a graceful shutdown does not hang on a slow handler.
+    pub async fn run_with_shutdown<F>(self, shutdown: F) -> Result<()>
+    where
+        F: std::future::Future<Output = ()> + Send,
+    {
+        let (sender, token) = CancelToken::channel();
+        let run = self.run_inner(Some(token));
+        tokio::pin!(run);
+
+        tokio::select! {
+            result = &mut run => result,
+            () = shutdown => {
+                let _ = sender.send(true);
+                run.await
+            }
+        }
+    }
+
+    async fn run_inner(self, shutdown: Option<CancelToken>) -> Result<()> {
+        for seed in self.seeds {
+            self.queue.write(seed?).await?;
+        }
+
+        let runner = Runner::new(
+            self.backend,
+            self.routes,
+            self.queue,
+            self.datasets,
+            self.limit,
+            self.batch,
+            self.hooks,
+            self.stats,
+            self.throttle,
+            self.adaptive,
+            shutdown,
+            self.idle,
+            self.preflight,
+            self.client_timeout,
+        );
+        runner.run().await
+    }
+}
+
+impl<B: Backend + fmt::Debug> fmt::Debug for Client<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("backend", &self.backend)
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::context::RequestQueue;
+    use crate::dataset::Data;
+    use crate::routing::Router;
+
+    async fn seed(queue: RequestQueue, data: Data<String>) -> Result<()> {
+        data.write("seed".to_owned()).await?;
+        queue.append_with_tag("leaf", "https://example.com/leaf").await?;
+        Ok(())
+    }
+
+    async fn leaf(data: Data<String>) -> Result<()> {
+        data.write("leaf".to_owned()).await
+    }
+
+    #[tokio::test]
+    async fn rejection_hook_observes_skips() {
+        use std::sync::Mutex;
+
+        use crate::extract::{FromContext, Rejection};
+
+        struct Never;
+
+        #[async_trait::async_trait]
+        impl<C: Send, S: Sync> FromContext<C, S> for Never {
+            type Rejection = Rejection;
+
+            async fn from_context(
+                _cx: &mut crate::context::Context<C>,
+                _state: &S,
+            ) -> Result<Self, Self::Rejection> {
+                Err(Rejection::new("Never: always rejects"))
+            }
+        }
+
+        async fn handler(_never: Never) {}
+
+        let seen: Arc<Mutex<Vec<(Tag, String)>>> = Arc::default();
+        let hook = {
+            let seen = seen.clone();
+            move |tag: &Tag, reason: &str| {
+                seen.lock().unwrap().push((tag.clone(), reason.to_owned()));
+            }
+        };
+
+        let router = Router::new().route("page", handler);
+        let client = Client::new(Noop::new(), router)
+            .with_rejection_hook(hook)
+            .with_initial_request("page", http::Request::get("https://example.com/").body(()).unwrap());
+        client.run().await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, Tag::from("page"));
+        assert!(seen[0].1.contains("Never: always rejects"));
+    }
+
+    #[tokio::test]
+    async fn panicking_handler_fails_only_its_request() {
+        async fn boom() {
+            panic!("boom");
+        }
+
+        let router = Router::new().route("boom", boom).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_seeds([("boom", "https://example.com/a"), ("leaf", "https://example.com/b")]);
+
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.read_all().await.unwrap(), vec!["leaf".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn batched_dequeue_drains_like_single() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_batch_size(4)
+            .with_seeds([
+                ("seed", "https://example.com/a"),
+                ("seed", "https://example.com/b"),
+            ]);
+
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 4);
+    }
+
+    #[tokio::test]
+    async fn tag_scoped_datasets_separate_records() {
+        async fn product(data: Data<String>) -> Result<()> {
+            data.write("product".to_owned()).await
+        }
+
+        async fn article(data: Data<String>) -> Result<()> {
+            data.write("article".to_owned()).await
+        }
+
+        let router = Router::new().route("product", product).route("article", article);
+        let products = InMemDataset::<String>::queue();
+        let articles = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset_for_tag("product", products.clone())
+            .with_dataset_for_tag("article", articles.clone())
+            .with_seeds([
+                ("product", "https://example.com/p"),
+                ("article", "https://example.com/a"),
+            ]);
+
+        client.run().await.unwrap();
+
+        assert_eq!(products.read().await.unwrap(), Some("product".to_owned()));
+        assert_eq!(articles.read().await.unwrap(), Some("article".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn tuple_extractor_grabs_three_datasets() {
+        use crate::extract::Datasets;
+
+        async fn handler(
+            Datasets((names, counts, flags)): Datasets<(Data<String>, Data<u64>, Data<bool>)>,
+        ) -> Result<()> {
+            names.write("name".to_owned()).await?;
+            counts.write(1).await?;
+            flags.write(true).await
+        }
+
+        let router = Router::new().route("page", handler);
+        let names = InMemDataset::<String>::queue();
+        let counts = InMemDataset::<u64>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(names.clone())
+            .with_dataset(counts.clone())
+            .with_seeds([("page", "https://example.com/")]);
+        client.run().await.unwrap();
+
+        assert_eq!(names.read().await.unwrap(), Some("name".to_owned()));
+        assert_eq!(counts.read().await.unwrap(), Some(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_cancels_slow_handlers() {
+        use crate::signal::CancelToken;
+
+        async fn slow(cancel: CancelToken, data: Data<String>) -> Result<()> {
+            tokio::select! {
+                () = tokio::time::sleep(std::time::Duration::from_secs(3600)) => {
+                    data.write("finished".to_owned()).await
+                }
+                () = cancel.cancelled() => data.write("cancelled".to_owned()).await,
+            }
+        }
+
+        let router = Router::new().route("slow", slow);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_seeds([("slow", "https://example.com/")]);
+
+        let shutdown = tokio::time::sleep(std::time::Duration::from_millis(50));
+        client.run_with_shutdown(shutdown).await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.read_all().await.unwrap(), vec!["cancelled".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn unfired_shutdown_drains_normally() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_seeds([("seed", "https://example.com/")]);
+
+        client.run_with_shutdown(std::future::pending()).await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttle_paces_dispatch() {
+        let router = Router::new().route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_throttle(10.0, 0.0)
+            .with_seeds([
+                ("leaf", "https://example.com/a"),
+                ("leaf", "https://example.com/b"),
+                ("leaf", "https://example.com/c"),
+            ]);
+
+        let started = tokio::time::Instant::now();
+        client.run().await.unwrap();
+
+        // Three requests at ten per second span two 100ms slots.
+        assert!(started.elapsed() >= std::time::Duration::from_millis(200));
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn dataset_handle_survives_the_run() {
+        let router = Router::new().route("leaf", leaf);
+        let client = Client::new(Noop::new(), router)
+            .with_seeds([("leaf", "https://example.com/")]);
+
+        let records = client.dataset::<String>();
+        client.run().await.unwrap();
+
+        assert_eq!(records.read_all().await.unwrap(), vec!["leaf".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn adaptive_concurrency_drains_queue() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_adaptive_concurrency(1, 8)
+            .with_seeds([
+                ("seed", "https://example.com/a"),
+                ("seed", "https://example.com/b"),
+            ]);
+
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 4);
+    }
+
+    #[tokio::test]
+    async fn events_stream_reports_crawl_progress() {
+        use futures_util::StreamExt;
+
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let client = Client::new(Noop::new(), router)
+            .with_seeds([("seed", "https://example.com/")]);
+
+        let events = client.events();
+        client.run().await.unwrap();
+
+        // The channel buffers past the end of the run, so the whole
+        // history can be drained afterwards.
+        let events: Vec<_> = events.collect().await;
+        let started = events
+            .iter()
+            .filter(|event| matches!(event, CrawlEvent::RequestStarted { .. }))
+            .count();
+        assert_eq!(started, 2);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            CrawlEvent::RequestEnqueued { tag, .. } if *tag == Tag::from("leaf"),
+        )));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            CrawlEvent::RequestFinished { outcome: CrawlOutcome::Continue, .. },
+        )));
+    }
+
+    #[tokio::test]
+    async fn head_preflight_filters_requests() {
+        use crate::context::ResponseExt;
+
+        let router = Router::new().route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_head_preflight(|response| response.content_length() != Some(0))
+            .with_seeds([("leaf", "https://example.com/")]);
+        client.run().await.unwrap();
+
+        // Noop answers the HEAD with an empty body, so the predicate
+        // refuses the full request.
+        assert_eq!(records.len().await, 0);
+
+        let router = Router::new().route("leaf", leaf);
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_head_preflight(|_response| true)
+            .with_seeds([("leaf", "https://example.com/")]);
+        client.run().await.unwrap();
+
+        assert_eq!(records.len().await, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout_delays_the_conclusion() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_idle_timeout(Duration::from_secs(30))
+            .with_seeds([("seed", "https://example.com/")]);
+
+        let started = tokio::time::Instant::now();
+        client.run().await.unwrap();
+
+        // The crawl still drains fully, but only concludes after a full
+        // quiet period on the empty queue.
+        assert!(started.elapsed() >= Duration::from_secs(30));
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn traversal_picks_the_crawl_shape() {
+        async fn handler(queue: RequestQueue, data: Data<String>) -> Result<()> {
+            data.write(queue.depth().to_string()).await?;
+            if queue.depth() == 0 {
+                queue.append("https://example.com/child").await?;
+            }
+            Ok(())
+        }
+
+        async fn ordered(traversal: Traversal) -> Vec<String> {
+            let router = Router::new().route(Tag::Fallback, handler);
+            let records = InMemDataset::<String>::queue();
+            let client = Client::new(Noop::new(), router)
+                .with_dataset(records.clone())
+                .with_traversal(traversal)
+                .with_concurrency_limit(1)
+                .with_seeds([
+                    (Tag::Fallback, "https://example.com/a"),
+                    (Tag::Fallback, "https://example.com/b"),
+                ]);
+            client.run().await.unwrap();
+            Data::new(records).read_all().await.unwrap()
+        }
+
+        // BFS finishes depth 0 before the children; DFS follows the
+        // first branch down before dispatching the second seed.
+        assert_eq!(ordered(Traversal::Bfs).await, vec!["0", "0", "1", "1"]);
+        assert_eq!(ordered(Traversal::Dfs).await, vec!["0", "1", "0", "1"]);
+    }
+
+    #[tokio::test]
+    async fn stop_signal_ends_the_crawl_early() {
+        use crate::signal::Signal;
+
+        async fn found(queue: RequestQueue, data: Data<String>) -> Result<Signal> {
+            data.write("found".to_owned()).await?;
+            queue.append("https://example.com/never").await?;
+            Ok(Signal::Stop)
+        }
+
+        let router = Router::new().route(Tag::Fallback, found);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_concurrency_limit(1)
+            .with_seeds([(Tag::Fallback, "https://example.com/target")]);
+
+        // The follow-up stays queued: the stop drains in flight work and
+        // concludes with `Ok` without dispatching anything further.
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.read_all().await.unwrap(), vec!["found".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn seeds_from_tagged_urls() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_seeds([("seed", "https://example.com/")]);
+
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        assert_eq!(data.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn invalid_seed_urls_fail_the_run() {
+        let router: Router<Noop> = Router::new();
+        let client = Client::new(Noop::new(), router).with_seed("seed", "not a url");
+
+        assert!(client.run().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn crawl_drains_queue_and_collects_records() {
+        let router = Router::new().route("seed", seed).route("leaf", leaf);
+        let records = InMemDataset::<String>::queue();
+
+        let client = Client::new(Noop::new(), router)
+            .with_dataset(records.clone())
+            .with_initial_request("seed", http::Request::get("https://example.com/").body(()).unwrap());
+
+        client.run().await.unwrap();
+
+        let data = Data::new(records);
+        let mut items = data.read_all().await.unwrap();
+        items.sort();
+        assert_eq!(items, vec!["leaf".to_owned(), "seed".to_owned()]);
+    }
+}
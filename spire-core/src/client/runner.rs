@@ -0,0 +1,421 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+
+use std::collections::VecDeque;
+
+use super::adaptive::AdaptiveConcurrency;
+use super::events::{CrawlEvent, CrawlOutcome};
+use super::throttle::Throttle;
+use super::HeadPredicate;
+use crate::backend::{Backend, Client, Fetcher};
+use crate::context::{Body, Context, QueueHooks, RequestSource, Tag, TagQuery, Task};
+use crate::dataset::{Data, DatasetBulkExt, DatasetRegistry};
+use crate::routing::Routes;
+use crate::signal::{CancelToken, Signal, SignalStats};
+use crate::{Error, ErrorKind, Result};
+
+/// Drives a crawl: pulls tasks off the queue and dispatches them through
+/// the routing table with bounded concurrency.
+///
+/// Dispatch order is the read order of the backing queue dataset; a
+/// priority-aware dataset makes the runner honor [`Task::priority`].
+pub(crate) struct Runner<B: Backend> {
+    backend: B,
+    routes: Routes<B::Client>,
+    queue: Data<Task>,
+    datasets: DatasetRegistry,
+    limit: usize,
+    batch: usize,
+    hooks: QueueHooks,
+    stats: SignalStats,
+    throttle: Option<Arc<Throttle>>,
+    adaptive: Option<Arc<AdaptiveConcurrency>>,
+    shutdown: Option<CancelToken>,
+    idle: Option<Duration>,
+    preflight: Option<HeadPredicate>,
+    client_timeout: Option<Duration>,
+    /// Queries of fatal errors; matching queued tasks are dropped.
+    cancelled: Arc<Mutex<Vec<TagQuery>>>,
+    /// Set once a handler returns [`Signal::Stop`]; ends dispatch.
+    stopped: AtomicBool,
+}
+
+impl<B: Backend> Runner<B> {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        backend: B,
+        routes: Routes<B::Client>,
+        queue: Data<Task>,
+        datasets: DatasetRegistry,
+        limit: usize,
+        batch: usize,
+        hooks: QueueHooks,
+        stats: SignalStats,
+        throttle: Option<Arc<Throttle>>,
+        adaptive: Option<Arc<AdaptiveConcurrency>>,
+        shutdown: Option<CancelToken>,
+        idle: Option<Duration>,
+        preflight: Option<HeadPredicate>,
+        client_timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            backend,
+            routes,
+            queue,
+            datasets,
+            limit,
+            batch: batch.max(1),
+            hooks,
+            stats,
+            throttle,
+            adaptive,
+            shutdown,
+            idle,
+            preflight,
+            client_timeout,
+            cancelled: Arc::default(),
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// Runs until the queue is observed empty with no tasks in flight.
+    pub(crate) async fn run(self) -> Result<()> {
+        let initial = self.adaptive.as_ref().map_or(self.limit, |adaptive| adaptive.target());
+        let semaphore = Arc::new(Semaphore::new(initial));
+        // Permits currently existing in the semaphore; adjusted towards
+        // the adaptive controller's target after every outcome.
+        let mut granted = initial;
+        let mut workers = JoinSet::new();
+        // Tasks dequeued ahead of dispatch; refilled `batch` at a time so
+        // slow queue datasets are hit once per batch, not once per task.
+        let mut pending = VecDeque::new();
+
+        loop {
+            while let Some(joined) = workers.try_join_next() {
+                self.handle_outcome(joined).await;
+            }
+            self.rebalance(&semaphore, &mut granted);
+
+            if self.shutdown.as_ref().is_some_and(CancelToken::is_cancelled) {
+                // Stop dispatching; the drain below waits out in-flight
+                // handlers, which observe the same token.
+                tracing::debug!("shutdown signal received, draining in-flight requests");
+                break;
+            }
+
+            if self.stopped.load(Ordering::Relaxed) {
+                // A handler found its target: same graceful drain as a
+                // shutdown, but initiated from inside the crawl.
+                tracing::debug!("stop signal received, draining in-flight requests");
+                break;
+            }
+
+            // Acquiring before the dequeue defers the queue read until a
+            // slot is actually free: under a limit of `1`, everything the
+            // previous request enqueued is visible to the next dequeue,
+            // which is what makes the configured traversal order hold.
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("semaphore never closes");
+
+            // The worker that freed this permit may have asked for a
+            // graceful stop; join it before dequeuing the next task so
+            // the stop takes effect without dispatching further work.
+            while let Some(joined) = workers.try_join_next() {
+                self.handle_outcome(joined).await;
+            }
+            if self.stopped.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if pending.is_empty() {
+                pending.extend(self.queue.read_bulk(self.batch).await?);
+            }
+
+            let Some(task) = pending.pop_front() else {
+                drop(permit);
+                // The queue is drained; wait out in-flight tasks, which may
+                // still enqueue follow-up work.
+                match workers.join_next().await {
+                    Some(joined) => {
+                        self.handle_outcome(joined).await;
+                        self.rebalance(&semaphore, &mut granted);
+                        continue;
+                    }
+                    None if self.await_new_work().await => continue,
+                    None => break,
+                }
+            };
+
+            if self.is_cancelled(&task) {
+                tracing::debug!(tag = %task.tag(), "dropping cancelled task");
+                continue;
+            }
+
+            // Reserving is instant; the wait is slept out in the worker
+            // so a paced host never stalls dispatch for the others.
+            let delay = self
+                .throttle
+                .as_ref()
+                .map(|throttle| throttle.reserve(task.request().uri()))
+                .unwrap_or_default();
+
+            let backend = self.backend.clone();
+            let routes = self.routes.clone();
+            let queue = self.queue.clone();
+            let datasets = self.datasets.clone();
+            let hooks = self.hooks.clone();
+            let stats = self.stats.clone();
+            let shutdown = self.shutdown.clone();
+            let preflight = self.preflight.clone();
+            let client_timeout = self.client_timeout;
+
+            workers.spawn(async move {
+                let _permit = permit;
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                Self::process(
+                    backend,
+                    routes,
+                    queue,
+                    datasets,
+                    hooks,
+                    stats,
+                    shutdown,
+                    preflight,
+                    client_timeout,
+                    task,
+                )
+                .await
+            });
+        }
+
+        while let Some(joined) = workers.join_next().await {
+            self.handle_outcome(joined).await;
+        }
+
+        Ok(())
+    }
+
+    /// Resolves and routes a single task, returning its outcome.
+    #[allow(clippy::too_many_arguments)]
+    async fn process(
+        backend: B,
+        routes: Routes<B::Client>,
+        queue: Data<Task>,
+        datasets: DatasetRegistry,
+        hooks: QueueHooks,
+        stats: SignalStats,
+        shutdown: Option<CancelToken>,
+        preflight: Option<HeadPredicate>,
+        client_timeout: Option<Duration>,
+        task: Task,
+    ) -> Signal {
+        let tag = task.tag().clone();
+        let uri = task.uri().clone();
+        let events = hooks.events.clone();
+        events.publish(|| CrawlEvent::RequestStarted {
+            tag: tag.clone(),
+            uri: uri.clone(),
+        });
+
+        let signal = async move {
+            let tag = task.tag().clone();
+            let Some(route) = routes.find(&tag) else {
+                tracing::debug!(%tag, "no route for tag");
+                return Signal::Skip;
+            };
+
+            let fetcher = Fetcher::new(backend.clone());
+            let acquired = match client_timeout {
+                Some(timeout) => backend.client_timeout(timeout).await,
+                None => backend.client().await,
+            };
+            let mut client = match acquired {
+                Ok(client) => client,
+                Err(error) => return Signal::Fail(error),
+            };
+
+            if let Some(skip) = Self::preflight_skips(&mut client, preflight, &task).await {
+                return skip;
+            }
+
+            let mut cx = Context::new(task, client, queue, datasets, hooks);
+            cx.set_fetcher(fetcher);
+            cx.set_signal_stats(stats);
+            if let Some(token) = shutdown {
+                cx.set_cancel_token(token);
+            }
+            match route.oneshot(cx).await {
+                Ok(signal) => signal,
+                Err(infallible) => match infallible {},
+            }
+        }
+        .await;
+
+        events.publish(|| CrawlEvent::RequestFinished {
+            tag,
+            uri,
+            outcome: CrawlOutcome::from(&signal),
+        });
+        signal
+    }
+
+    /// Runs the configured `HEAD` preflight, returning the signal to
+    /// short-circuit with when the predicate refuses the request.
+    ///
+    /// A failing `HEAD` proceeds with the full request — plenty of
+    /// servers answer `HEAD` poorly, and the preflight is an
+    /// optimization, not a gate.
+    async fn preflight_skips(
+        client: &mut B::Client,
+        preflight: Option<HeadPredicate>,
+        task: &Task,
+    ) -> Option<Signal> {
+        let predicate = preflight?;
+        if !client.supports_head() || task.request().method() != http::Method::GET {
+            return None;
+        }
+
+        let head = http::Request::builder()
+            .method(http::Method::HEAD)
+            .uri(task.uri().clone())
+            .body(Body::empty())
+            .expect("rebuilt request parts stay valid");
+
+        match client.resolve(head).await {
+            Ok(response) if !predicate(&response) => {
+                tracing::debug!(uri = %task.uri(), "preflight predicate skipped the request");
+                Some(Signal::Skip)
+            }
+            Ok(_) => None,
+            Err(error) => {
+                tracing::debug!(%error, "preflight HEAD failed; dispatching the full request");
+                None
+            }
+        }
+    }
+
+    /// Records the outcome of a finished worker.
+    async fn handle_outcome(&self, joined: Result<Signal, tokio::task::JoinError>) {
+        let signal = match joined {
+            Ok(signal) => signal,
+            Err(error) if error.is_panic() => {
+                // A panicking handler fails its own request; the spawned
+                // task already unwound, so no dataset lock is poisoned and
+                // the rest of the crawl proceeds.
+                let reason = Self::panic_reason(error.into_panic());
+                Signal::Fail(Error::msg(
+                    ErrorKind::Context,
+                    format!("handler panicked: {reason}"),
+                ))
+            }
+            Err(error) => {
+                tracing::error!(%error, "worker task aborted");
+                return;
+            }
+        };
+
+        self.stats.record(&signal);
+        if let Some(adaptive) = &self.adaptive {
+            adaptive.record(&signal);
+        }
+
+        match signal {
+            Signal::Continue => {}
+            Signal::Skip => tracing::trace!("request skipped"),
+            Signal::Wait(delay) => tokio::time::sleep(delay).await,
+            Signal::Fail(error) => {
+                tracing::error!(%error, "request failed");
+                if let Some(query) = error.query() {
+                    let mut guard = self.cancelled.lock().expect("cancel list lock poisoned");
+                    guard.push(query.clone());
+                }
+            }
+            Signal::Stop => {
+                tracing::debug!("handler requested a graceful stop");
+                self.stopped.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Waits up to the idle timeout for another worker to enqueue work,
+    /// returning whether the dispatch loop should poll the queue again.
+    ///
+    /// Without an idle timeout the first empty observation (with nothing
+    /// in flight) ends the crawl, which is right for a single runner but
+    /// premature when other producers feed the same queue dataset.
+    async fn await_new_work(&self) -> bool {
+        let Some(idle) = self.idle else {
+            return false;
+        };
+
+        tracing::debug!(?idle, "queue empty, idling for new work");
+        let wakeup = self.hooks.wakeup.notified();
+        match &self.shutdown {
+            Some(token) => tokio::select! {
+                notified = tokio::time::timeout(idle, wakeup) => notified.is_ok(),
+                () = token.cancelled() => false,
+            },
+            None => tokio::time::timeout(idle, wakeup).await.is_ok(),
+        }
+    }
+
+    /// Adjusts the semaphore towards the adaptive controller's target.
+    ///
+    /// Growing is immediate; shrinking only reclaims permits that are
+    /// currently free, so in-flight requests finish undisturbed and the
+    /// next rebalance picks up whatever they release.
+    fn rebalance(&self, semaphore: &Semaphore, granted: &mut usize) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+
+        let target = adaptive.target();
+        if *granted < target {
+            semaphore.add_permits(target - *granted);
+            *granted = target;
+        }
+
+        while *granted > target {
+            match semaphore.try_acquire() {
+                Ok(permit) => {
+                    permit.forget();
+                    *granted -= 1;
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Extracts a printable message from a panic payload.
+    fn panic_reason(payload: Box<dyn std::any::Any + Send>) -> String {
+        match payload.downcast_ref::<&str>() {
+            Some(message) => (*message).to_owned(),
+            None => match payload.downcast_ref::<String>() {
+                Some(message) => message.clone(),
+                None => "non-string panic payload".to_owned(),
+            },
+        }
+    }
+
+    /// Returns `true` if the task matches a recorded cancellation query.
+    fn is_cancelled(&self, task: &Task) -> bool {
+        let guard = self.cancelled.lock().expect("cancel list lock poisoned");
+        if guard.is_empty() {
+            return false;
+        }
+
+        let tag: &Tag = task.tag();
+        let source = task.request().extensions().get::<RequestSource>();
+        guard.iter().any(|query| query.matches_task(tag, source))
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use http::Uri;
+use tokio::time::Instant;
+
+/// Paces dispatch to at most a fixed number of requests per second,
+/// crawl-wide and per host.
+///
+/// Reservation-based: each task reserves the next free timeslot up front
+/// and its worker sleeps out the difference, so reserving never blocks
+/// the dispatch loop and slots are handed out in dispatch order.
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    global: Option<Gate>,
+    per_host: Option<HostGates>,
+}
+
+/// A single pacing gate: the next free timeslot and the slot spacing.
+#[derive(Debug)]
+struct Gate {
+    interval: Duration,
+    next_free: Mutex<Instant>,
+}
+
+/// Per-host pacing gates, created lazily per host.
+///
+/// The map grows with the number of distinct hosts seen; entries are
+/// never evicted, which stays negligible next to the crawl state itself.
+#[derive(Debug)]
+struct HostGates {
+    interval: Duration,
+    next_free: Mutex<HashMap<String, Instant>>,
+}
+
+impl Throttle {
+    /// Creates a throttle from requests-per-second rates; a non-positive
+    /// rate leaves that dimension unlimited.
+    pub(crate) fn new(global_rps: f64, per_host_rps: f64) -> Self {
+        let interval = |rps: f64| (rps > 0.0).then(|| Duration::from_secs_f64(1.0 / rps));
+
+        Self {
+            global: interval(global_rps).map(|interval| Gate {
+                interval,
+                next_free: Mutex::new(Instant::now()),
+            }),
+            per_host: interval(per_host_rps).map(|interval| HostGates {
+                interval,
+                next_free: Mutex::default(),
+            }),
+        }
+    }
+
+    /// Reserves the next free timeslot for a request to `uri`, returning
+    /// how long the worker must wait before sending it.
+    pub(crate) fn reserve(&self, uri: &Uri) -> Duration {
+        let now = Instant::now();
+        let mut wait = Duration::ZERO;
+
+        if let Some(gate) = &self.global {
+            let mut next_free = gate.next_free.lock().expect("throttle lock poisoned");
+            let slot = (*next_free).max(now);
+            *next_free = slot + gate.interval;
+            wait = wait.max(slot.duration_since(now));
+        }
+
+        if let (Some(gates), Some(host)) = (&self.per_host, uri.host()) {
+            let mut next_free = gates.next_free.lock().expect("throttle lock poisoned");
+            let slot = (*next_free.entry(host.to_owned()).or_insert(now)).max(now);
+            next_free.insert(host.to_owned(), slot + gates.interval);
+            wait = wait.max(slot.duration_since(now));
+        }
+
+        wait
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn uri(uri: &str) -> Uri {
+        uri.parse().expect("test uri should parse")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn global_slots_are_spaced_by_the_interval() {
+        let throttle = Throttle::new(10.0, 0.0);
+        let target = uri("https://example.com/");
+
+        assert_eq!(throttle.reserve(&target), Duration::ZERO);
+        assert_eq!(throttle.reserve(&target), Duration::from_millis(100));
+        assert_eq!(throttle.reserve(&target), Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hosts_are_paced_independently() {
+        let throttle = Throttle::new(0.0, 2.0);
+
+        assert_eq!(throttle.reserve(&uri("https://a.com/")), Duration::ZERO);
+        assert_eq!(throttle.reserve(&uri("https://b.com/")), Duration::ZERO);
+        assert_eq!(
+            throttle.reserve(&uri("https://a.com/next")),
+            Duration::from_millis(500),
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tighter_limit_wins() {
+        let throttle = Throttle::new(10.0, 1.0);
+        let target = uri("https://example.com/");
+
+        assert_eq!(throttle.reserve(&target), Duration::ZERO);
+        // The per-host second is stricter than the global tenth.
+        assert_eq!(throttle.reserve(&target), Duration::from_secs(1));
+    }
+}
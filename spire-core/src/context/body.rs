@@ -0,0 +1,152 @@
+use bytes::Bytes;
+use http::header;
+use mime::Mime;
+
+/// Buffered request or response payload.
+///
+/// Bodies are fully buffered in memory; backends collect the remote payload
+/// before handing the [`Response`] to the routing machinery.
+///
+/// [`Response`]: crate::context::Response
+#[derive(Debug, Clone, Default)]
+pub struct Body {
+    inner: Bytes,
+}
+
+impl Body {
+    /// Creates an empty body.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Creates a body from the given buffer.
+    pub fn new(data: impl Into<Bytes>) -> Self {
+        Self { inner: data.into() }
+    }
+
+    /// Returns the length of the buffered payload in bytes.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the buffered payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Consumes the body, returning the underlying buffer.
+    pub fn into_bytes(self) -> Bytes {
+        self.inner
+    }
+
+    /// Borrows the buffered payload without consuming the body.
+    pub(crate) fn bytes(&self) -> &Bytes {
+        &self.inner
+    }
+}
+
+/// Response payload buffered by [`Context::buffer_body`], stored in the
+/// context extensions so later body reads can hand out copies.
+///
+/// [`Context::buffer_body`]: crate::context::Context::buffer_body
+#[derive(Debug, Clone)]
+pub(crate) struct BufferedBody(pub(crate) Bytes);
+
+/// Cheap header- and length-based inspection of a [`Response`].
+///
+/// Lets routing and extraction decisions (content-type dispatch,
+/// size-based skips) happen without consuming the body.
+///
+/// [`Response`]: crate::context::Response
+pub trait ResponseExt {
+    /// Returns the parsed `Content-Type` header, if present and valid.
+    fn content_type(&self) -> Option<Mime>;
+
+    /// Returns the length of the payload in bytes.
+    ///
+    /// Prefers the `Content-Length` header and falls back to the length
+    /// of the buffered body, so the returned size reflects what the
+    /// server advertised where possible.
+    fn content_length(&self) -> Option<u64>;
+}
+
+impl ResponseExt for http::Response<Body> {
+    fn content_type(&self) -> Option<Mime> {
+        let value = self.headers().get(header::CONTENT_TYPE)?;
+        value.to_str().ok()?.parse().ok()
+    }
+
+    fn content_length(&self) -> Option<u64> {
+        let advertised = self
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+
+        advertised.or(Some(self.body().len() as u64))
+    }
+}
+
+impl From<()> for Body {
+    fn from((): ()) -> Self {
+        Self::empty()
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(data: Bytes) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<String> for Body {
+    fn from(data: String) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(data: &'static str) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<&'static [u8]> for Body {
+    fn from(data: &'static [u8]) -> Self {
+        Self::new(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn length_tracks_the_buffer() {
+        assert!(Body::empty().is_empty());
+        assert_eq!(Body::new("abc").len(), 3);
+    }
+
+    #[test]
+    fn response_inspection_reads_headers() {
+        let response = http::Response::builder()
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .header(header::CONTENT_LENGTH, "120")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(response.content_type(), Some(mime::TEXT_HTML_UTF_8));
+        assert_eq!(response.content_length(), Some(120));
+
+        // Without headers the buffered body supplies the length.
+        let response = http::Response::new(Body::new("abc"));
+        assert_eq!(response.content_type(), None);
+        assert_eq!(response.content_length(), Some(3));
+    }
+}
@@ -0,0 +1,442 @@
+//! Per-request context and its building blocks.
+
+use std::time::Duration;
+
+use http::Uri;
+
+use crate::backend::{Client, Fetcher};
+use crate::dataset::{Data, DatasetRegistry};
+use crate::signal::{CancelToken, SignalStats};
+use crate::{Error, ErrorKind, Result};
+
+mod body;
+mod page;
+mod queue;
+mod tag;
+mod task;
+
+pub(crate) use body::BufferedBody;
+pub use body::{Body, ResponseExt};
+pub use page::{PageContent, PageText, PageTitle};
+pub(crate) use queue::{LoopGuard, QueueHooks};
+pub use queue::{normalize_uri, RejectionHook, RequestQueue, UrlNormalizer};
+pub use tag::{Tag, TagQuery};
+pub use task::{Depth, Lastmod, Priority, RequestSource, Task, TaskBuilder};
+
+/// An outgoing request processed by the framework.
+pub type Request = http::Request<Body>;
+
+/// A backend response to a crawled request.
+pub type Response = http::Response<Body>;
+
+/// Per-request processing context handed to routes and middleware.
+///
+/// A context owns the outgoing [`Request`], the backend client acquired for
+/// it, and handles to the request queue and the shared dataset registry.
+/// Middleware may inspect or mutate the request before it is resolved; the
+/// first call to [`Context::resolve`] sends it and caches the [`Response`].
+pub struct Context<C> {
+    client: C,
+    request: Option<Request>,
+    response: Option<Response>,
+    body_taken: bool,
+    tag: Tag,
+    uri: Uri,
+    depth: usize,
+    queue: RequestQueue,
+    datasets: DatasetRegistry,
+    fetcher: Option<Fetcher>,
+    rejection_hook: Option<RejectionHook>,
+    extensions: http::Extensions,
+    signal_stats: Option<SignalStats>,
+    cancel: Option<CancelToken>,
+}
+
+impl<C> Context<C> {
+    pub(crate) fn new(
+        task: Task,
+        client: C,
+        queue: Data<Task>,
+        datasets: DatasetRegistry,
+        hooks: QueueHooks,
+    ) -> Self {
+        let depth = task.depth();
+        let (tag, request) = task.into_parts();
+        let uri = request.uri().clone();
+        let rejection_hook = hooks.rejection.clone();
+        let queue = RequestQueue::new(queue, tag.clone(), uri.clone(), depth, hooks);
+
+        Self {
+            client,
+            request: Some(request),
+            response: None,
+            body_taken: false,
+            tag,
+            uri,
+            depth,
+            queue,
+            datasets,
+            fetcher: None,
+            rejection_hook,
+            extensions: http::Extensions::new(),
+            signal_stats: None,
+            cancel: None,
+        }
+    }
+
+    pub(crate) fn set_fetcher(&mut self, fetcher: Fetcher) {
+        self.fetcher = Some(fetcher);
+    }
+
+    pub(crate) fn set_signal_stats(&mut self, stats: SignalStats) {
+        self.signal_stats = Some(stats);
+    }
+
+    pub(crate) fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = Some(cancel);
+    }
+
+    /// Returns the cooperative cancellation token, when running under
+    /// [`Client::run_with_shutdown`].
+    ///
+    /// [`Client::run_with_shutdown`]: crate::client::Client::run_with_shutdown
+    pub fn cancel_token(&self) -> Option<CancelToken> {
+        self.cancel.clone()
+    }
+
+    /// Returns the rolling window of recent request outcomes, when
+    /// running under the framework's run loop.
+    pub fn signal_stats(&self) -> Option<SignalStats> {
+        self.signal_stats.clone()
+    }
+
+    /// Returns a handle for fetching auxiliary resources through the
+    /// backend, when running under the framework's run loop.
+    pub fn fetcher(&self) -> Option<Fetcher> {
+        self.fetcher.clone()
+    }
+
+    /// Invokes the configured rejection hook, if any.
+    pub(crate) fn notify_rejection(&self, reason: &str) {
+        if let Some(hook) = &self.rejection_hook {
+            hook(&self.tag, reason);
+        }
+    }
+
+    /// Returns the routing tag of the current request.
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns the URI the current request was enqueued with.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Returns the crawling depth of the current request.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns a handle for enqueueing follow-up requests.
+    pub fn queue(&self) -> RequestQueue {
+        self.queue.clone()
+    }
+
+    /// Returns the typed dataset handle for `T`, creating an in-memory
+    /// dataset on first access.
+    ///
+    /// A dataset bound to the current routing tag (see
+    /// [`Client::with_dataset_for_tag`]) takes precedence over the
+    /// crawl-wide one.
+    ///
+    /// [`Client::with_dataset_for_tag`]: crate::client::Client::with_dataset_for_tag
+    pub fn dataset<T: Send + Sync + 'static>(&self) -> Data<T> {
+        self.datasets.get_scoped(self.tag())
+    }
+
+    /// Returns the shared dataset registry.
+    pub fn datasets(&self) -> &DatasetRegistry {
+        &self.datasets
+    }
+
+    /// Returns the pending request, unless it was already resolved.
+    pub fn request(&self) -> Option<&Request> {
+        self.request.as_ref()
+    }
+
+    /// Returns a mutable reference to the pending request, unless it was
+    /// already resolved.
+    ///
+    /// The escape hatch for arbitrary edits; for the common ones see
+    /// [`Context::set_header`], [`Context::set_method`] and
+    /// [`Context::set_uri`].
+    pub fn get_mut(&mut self) -> Option<&mut Request> {
+        self.request.as_mut()
+    }
+
+    /// Sets a header on the pending request, replacing any previous value.
+    ///
+    /// Returns `false` when the request was already resolved, like the
+    /// other `set_*` helpers: a no-op on an already-sent request is
+    /// reported rather than silently swallowed.
+    pub fn set_header(&mut self, name: http::HeaderName, value: http::HeaderValue) -> bool {
+        match self.request.as_mut() {
+            Some(request) => {
+                request.headers_mut().insert(name, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the method of the pending request.
+    ///
+    /// Returns `false` when the request was already resolved.
+    pub fn set_method(&mut self, method: http::Method) -> bool {
+        match self.request.as_mut() {
+            Some(request) => {
+                *request.method_mut() = method;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the URI of the pending request.
+    ///
+    /// The context keeps reporting the URI the request was enqueued with
+    /// through [`Context::uri`]; only the outgoing request changes.
+    /// Returns `false` when the request was already resolved.
+    pub fn set_uri(&mut self, uri: Uri) -> bool {
+        match self.request.as_mut() {
+            Some(request) => {
+                *request.uri_mut() = uri;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the cached response, if the request was resolved.
+    pub fn response(&self) -> Option<&Response> {
+        self.response.as_ref()
+    }
+
+    /// Stashes a typed value on the current request, returning the value
+    /// it replaced.
+    ///
+    /// Extensions are keyed by type — one slot per `T` — and live in the
+    /// [`http::Extensions`] of the request, next to the framework's own
+    /// markers like [`Depth`]. Values inserted before resolution travel
+    /// with the request to the backend and stay retrievable afterwards,
+    /// so an early layer can pass computed state (auth tokens, parsed
+    /// configuration) to later layers and handlers.
+    pub fn insert_extension<T>(&mut self, value: T) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        match self.request.as_mut() {
+            Some(request) => request.extensions_mut().insert(value),
+            None => self.extensions.insert(value),
+        }
+    }
+
+    /// Returns the extension of type `T` stashed on the current request,
+    /// if any. See [`Context::insert_extension`].
+    pub fn get_extension<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        match self.request.as_ref() {
+            Some(request) => request.extensions().get(),
+            None => self.extensions.get(),
+        }
+    }
+
+    /// Returns a reference to the backend client of this context.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns a mutable reference to the backend client of this context.
+    pub fn client_mut(&mut self) -> &mut C {
+        &mut self.client
+    }
+}
+
+impl<C: Client> Context<C> {
+    /// Resolves the pending request through the backend client.
+    ///
+    /// The response is cached; repeated calls return the same response
+    /// without touching the backend again.
+    pub async fn resolve(&mut self) -> Result<&Response> {
+        if self.response.is_none() {
+            let request = self.request.take().ok_or_else(|| {
+                Error::msg(ErrorKind::Context, "request was already consumed")
+            })?;
+            // Keep the request extensions retrievable after the backend
+            // consumed the request itself.
+            self.extensions = request.extensions().clone();
+            let response = self.client.resolve(request).await?;
+            self.response = Some(response);
+        }
+
+        Ok(self.response.as_ref().expect("response was just cached"))
+    }
+
+    /// Resolves the pending request, giving the backend at most `timeout`.
+    ///
+    /// Returns an [`ErrorKind::Timeout`] error when the timer expires
+    /// first. The in-flight exchange is dropped on expiry, so a timed-out
+    /// context cannot be resolved again.
+    pub async fn resolve_with_timeout(&mut self, timeout: Duration) -> Result<&Response> {
+        match tokio::time::timeout(timeout, self.resolve()).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::msg(
+                ErrorKind::Timeout,
+                format!("request timed out after {timeout:?}"),
+            )),
+        }
+    }
+
+    /// Resolves an auxiliary request through the backend client, without
+    /// touching the pending request or the cached response.
+    pub async fn resolve_request(&mut self, request: Request) -> Result<Response> {
+        self.client.resolve(request).await
+    }
+
+    /// Takes the response body, resolving the pending request first.
+    ///
+    /// The body can only be taken once; later calls return a
+    /// [`ErrorKind::Context`] error. See [`Context::buffer_body`] for
+    /// opting into repeated reads.
+    pub async fn body(&mut self) -> Result<Body> {
+        self.resolve().await?;
+        if let Some(buffered) = self.extensions.get::<BufferedBody>() {
+            return Ok(Body::new(buffered.0.clone()));
+        }
+        if self.body_taken {
+            return Err(Error::msg(ErrorKind::Context, "body was already consumed"));
+        }
+
+        self.body_taken = true;
+        let response = self.response.as_mut().expect("response was just cached");
+        Ok(std::mem::take(response.body_mut()))
+    }
+
+    /// Buffers the response body so it can be consumed more than once.
+    ///
+    /// Reads the body into the context extensions once; afterwards every
+    /// [`Context::body`] call hands out a copy of the buffered payload,
+    /// so several body-based extractors — say a JSON attempt with a
+    /// plain-text fallback — can run against the same response without a
+    /// second fetch. The copies are cheap reference-counted buffers, but
+    /// the payload stays in memory for the rest of the request, which is
+    /// why buffering is opt-in. Calling this after the body was already
+    /// consumed fails like a second [`Context::body`] call would.
+    pub async fn buffer_body(&mut self) -> Result<()> {
+        if self.extensions.get::<BufferedBody>().is_none() {
+            let body = self.body().await?;
+            self.extensions.insert(BufferedBody(body.into_bytes()));
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> std::fmt::Debug for Context<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Context")
+            .field("tag", &self.tag)
+            .field("uri", &self.uri)
+            .field("depth", &self.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::{context_for, StaticClient};
+
+    /// Client whose resolve never completes.
+    #[derive(Debug, Clone)]
+    struct Stalled;
+
+    #[async_trait]
+    impl Client for Stalled {
+        async fn resolve(&mut self, _request: Request) -> Result<Response> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn extensions_survive_resolution() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct AuthToken(String);
+
+        let (mut cx, _queue) = context_for("https://example.com/", Noop::new());
+        cx.insert_extension(AuthToken("secret".to_owned()));
+        assert!(cx.resolve().await.is_ok());
+
+        let token = cx.get_extension::<AuthToken>();
+        assert_eq!(token, Some(&AuthToken("secret".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn buffered_body_supports_repeated_reads() {
+        let client = StaticClient::new("/", "payload");
+        let (mut cx, _queue) = context_for("https://example.com/", client);
+
+        cx.buffer_body().await.unwrap();
+        let first = cx.body().await.unwrap();
+        let second = cx.body().await.unwrap();
+        assert_eq!(first.into_bytes(), second.into_bytes());
+
+        // Without buffering the second read still fails.
+        let client = StaticClient::new("/", "payload");
+        let (mut cx, _queue) = context_for("https://example.com/", client);
+        cx.body().await.unwrap();
+        assert!(cx.body().await.is_err());
+        assert!(cx.buffer_body().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn request_edit_helpers_stop_after_resolution() {
+        let (mut cx, _queue) = context_for("https://example.com/", Noop::new());
+        assert!(cx.set_method(http::Method::POST));
+        assert!(cx.set_header(
+            http::header::AUTHORIZATION,
+            http::HeaderValue::from_static("Bearer token"),
+        ));
+
+        let request = cx.request().unwrap();
+        assert_eq!(request.method(), http::Method::POST);
+        assert!(request.headers().contains_key(http::header::AUTHORIZATION));
+
+        cx.resolve().await.unwrap();
+        assert!(!cx.set_method(http::Method::GET));
+        assert!(!cx.set_uri(Uri::from_static("https://example.com/other")));
+    }
+
+    #[tokio::test]
+    async fn resolve_within_timeout() {
+        let (mut cx, _queue) = context_for("https://example.com/", Noop::new());
+        let response = cx.resolve_with_timeout(Duration::from_secs(1)).await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn resolve_timeout_expires() {
+        let (mut cx, _queue) = context_for("https://example.com/", Stalled);
+        let error = cx
+            .resolve_with_timeout(Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Timeout);
+    }
+}
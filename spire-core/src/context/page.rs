@@ -0,0 +1,40 @@
+/// The document title of a rendered page, attached by a backend as a
+/// response extension.
+///
+/// Backends that render pages (such as the browser backend) insert this
+/// into [`Response::extensions`] so handlers can read the title without
+/// re-parsing the body.
+///
+/// [`Response::extensions`]: http::Response::extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageTitle(pub String);
+
+/// The visible text of a rendered page, attached by a backend as a
+/// response extension.
+///
+/// Present when a backend extracted both the document and its text but
+/// could only store one of them as the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageText(pub String);
+
+/// Everything a rendering backend extracted from a page in one pass,
+/// attached as a response extension.
+///
+/// Extractable in handlers, where it replaces separate driver calls for
+/// the source, text, title and final URL — each of which would be
+/// another WebDriver round-trip. `html` and `text` mirror the backend's
+/// extraction toggles: a field is `None` when that extraction was
+/// disabled. The extracted HTML also serves as the response body, so
+/// taking it from here instead leaves the body free for other
+/// extractors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageContent {
+    /// The rendered document source, when HTML extraction is enabled.
+    pub html: Option<String>,
+    /// The visible page text, when text extraction is enabled.
+    pub text: Option<String>,
+    /// The document title.
+    pub title: String,
+    /// The URL the page ended up on, redirects applied.
+    pub url: String,
+}
@@ -0,0 +1,549 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use http::Uri;
+use tokio::sync::Notify;
+
+use crate::client::events::{CrawlEvent, EventPublisher};
+use crate::context::{RequestSource, Tag, Task, TaskBuilder};
+use crate::dataset::{Data, DatasetBulkExt};
+use crate::{Error, Result};
+
+/// Shared hook canonicalizing URLs before they are enqueued.
+///
+/// Registered via [`Client::with_url_normalizer`] and applied by
+/// [`RequestQueue::append`] / [`RequestQueue::append_with_tag`] before the
+/// task is stored.
+///
+/// [`Client::with_url_normalizer`]: crate::client::Client::with_url_normalizer
+pub type UrlNormalizer = Arc<dyn Fn(Uri) -> Uri + Send + Sync>;
+
+/// Canonicalizes a URI by stripping its fragment and sorting its query
+/// parameters.
+///
+/// Opt-in default for [`Client::with_url_normalizer`]; collapses URL forms
+/// that only differ in parameter order into one queue entry.
+///
+/// [`Client::with_url_normalizer`]: crate::client::Client::with_url_normalizer
+pub fn normalize_uri(uri: Uri) -> Uri {
+    let Some(query) = uri.query() else {
+        return uri;
+    };
+
+    let mut params: Vec<&str> = query.split('&').collect();
+    params.sort_unstable();
+
+    let path_and_query = format!("{}?{}", uri.path(), params.join("&"));
+    let mut parts = uri.into_parts();
+    match path_and_query.parse() {
+        Ok(sorted) => parts.path_and_query = Some(sorted),
+        Err(_) => return Uri::from_parts(parts).expect("unchanged parts stay valid"),
+    }
+
+    Uri::from_parts(parts).expect("re-assembled uri stays valid")
+}
+
+/// Crawl-wide guard breaking loops over infinite URL spaces.
+///
+/// Fingerprints each enqueued URL by its host, path and the *keys* of
+/// its query parameters — dropping the values, which is what varies on
+/// calendar pages, session ids and the like. Once a fingerprint has been
+/// enqueued `max` times, further appends matching it are refused.
+///
+/// Registered via [`Client::with_loop_guard`].
+///
+/// [`Client::with_loop_guard`]: crate::client::Client::with_loop_guard
+#[derive(Debug)]
+pub(crate) struct LoopGuard {
+    max: usize,
+    seen: Mutex<HashMap<u64, usize>>,
+}
+
+impl LoopGuard {
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            seen: Mutex::default(),
+        }
+    }
+
+    /// Records an enqueue of `uri`, returning `false` once its
+    /// fingerprint is over the limit.
+    fn admit(&self, uri: &Uri) -> bool {
+        let fingerprint = Self::fingerprint(uri);
+        let mut seen = self.seen.lock().expect("loop guard lock poisoned");
+        let count = seen.entry(fingerprint).or_insert(0);
+        *count += 1;
+
+        if *count == self.max + 1 {
+            // Log once per fingerprint, at the moment it tips over.
+            tracing::warn!(%uri, limit = self.max, "loop guard throttled a recurring url shape");
+        }
+
+        *count <= self.max
+    }
+
+    /// Hashes the loop-relevant parts of a URI: host, path and sorted
+    /// query parameter keys, with the values dropped.
+    fn fingerprint(uri: &Uri) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        uri.host().hash(&mut hasher);
+        uri.path().hash(&mut hasher);
+
+        let mut keys: Vec<&str> = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .map(|param| param.split_once('=').map_or(param, |(key, _)| key))
+            .collect();
+        keys.sort_unstable();
+        keys.hash(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+/// Crawl-wide hooks applied by every [`RequestQueue`].
+///
+/// Assembled by the [`Client`] builder methods and threaded into each
+/// per-request context by the runner.
+///
+/// [`Client`]: crate::client::Client
+#[derive(Clone, Default)]
+pub(crate) struct QueueHooks {
+    pub(crate) normalizer: Option<UrlNormalizer>,
+    pub(crate) link_graph: Option<Data<(Uri, Uri)>>,
+    pub(crate) rejection: Option<RejectionHook>,
+    pub(crate) loop_guard: Option<Arc<LoopGuard>>,
+    pub(crate) auto_referer: bool,
+    /// Pinged on every append; lets an idling runner wake up without
+    /// polling the queue dataset.
+    pub(crate) wakeup: Arc<Notify>,
+    /// Sender side of the crawl event channel; see [`Client::events`].
+    ///
+    /// [`Client::events`]: crate::client::Client::events
+    pub(crate) events: EventPublisher,
+}
+
+/// Shared callback invoked when an extractor rejection skips a request.
+///
+/// Receives the routing tag of the skipped request and the rejection
+/// reason; see [`Client::with_rejection_hook`].
+///
+/// [`Client::with_rejection_hook`]: crate::client::Client::with_rejection_hook
+pub type RejectionHook = Arc<dyn Fn(&Tag, &str) + Send + Sync>;
+
+/// Handle for enqueueing follow-up requests from a handler.
+///
+/// Appended requests inherit the crawling depth of the current request plus
+/// one, and record the current request as their [`RequestSource`]. The
+/// `append*` methods return whether the request was actually enqueued:
+/// a handle capped via [`RequestQueue::with_max_depth`] refuses appends
+/// that would exceed the cap and reports them as `Ok(false)`.
+#[derive(Clone)]
+pub struct RequestQueue {
+    dataset: Data<Task>,
+    tag: Tag,
+    uri: Uri,
+    depth: usize,
+    max_depth: Option<usize>,
+    hooks: QueueHooks,
+}
+
+impl RequestQueue {
+    pub(crate) fn new(
+        dataset: Data<Task>,
+        tag: Tag,
+        uri: Uri,
+        depth: usize,
+        hooks: QueueHooks,
+    ) -> Self {
+        Self {
+            dataset,
+            tag,
+            uri,
+            depth,
+            max_depth: None,
+            hooks,
+        }
+    }
+
+    /// Caps the crawling depth this handle enqueues at.
+    ///
+    /// Appends that would exceed the cap are dropped and reported as
+    /// `Ok(false)`. The cap is local to this handle (and its clones) —
+    /// a per-queue scope, not a crawl-wide limit: a handler following
+    /// pagination can cap its own fan-out while other handlers keep
+    /// enqueueing freely.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Enqueues a request under the current task's tag.
+    ///
+    /// Returns whether the request was enqueued; see
+    /// [`RequestQueue::with_max_depth`].
+    pub async fn append(&self, uri: impl AsRef<str>) -> Result<bool> {
+        self.append_with_tag(self.tag.clone(), uri).await
+    }
+
+    /// Enqueues a request under an explicit tag.
+    ///
+    /// Returns whether the request was enqueued; see
+    /// [`RequestQueue::with_max_depth`].
+    pub async fn append_with_tag(&self, tag: impl Into<Tag>, uri: impl AsRef<str>) -> Result<bool> {
+        if self.exceeds_max_depth() {
+            tracing::trace!(uri = uri.as_ref(), "append refused by the depth cap");
+            return Ok(false);
+        }
+
+        let mut task = self.build_task(tag.into(), uri.as_ref())?;
+        if self.refused_by_loop_guard(task.uri()) {
+            return Ok(false);
+        }
+        self.stamp_referer(&mut task);
+
+        if let Some(graph) = &self.hooks.link_graph {
+            graph.write((self.uri.clone(), task.uri().clone())).await?;
+        }
+
+        self.publish_enqueued(&task);
+        self.dataset.write(task).await?;
+        self.hooks.wakeup.notify_one();
+        Ok(true)
+    }
+
+    /// Enqueues many requests under an explicit tag in one batched write.
+    ///
+    /// URLs that fail to parse are collected and returned alongside their
+    /// error instead of aborting the rest of the batch; the depth cap and
+    /// loop guard drop entries exactly as they do for the single appends.
+    /// Backed by [`DatasetBulkExt::write_bulk`], so a storage-backed
+    /// queue is hit once per batch rather than once per URL — the cheaper
+    /// path for handlers expanding many links at once.
+    pub async fn append_many<I>(
+        &self,
+        tag: impl Into<Tag>,
+        urls: I,
+    ) -> Result<Vec<(String, Error)>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut failed = Vec::new();
+        if self.exceeds_max_depth() {
+            tracing::trace!("bulk append refused by the depth cap");
+            return Ok(failed);
+        }
+
+        let tag = tag.into();
+        let mut batch = Vec::new();
+        for uri in urls {
+            let uri = uri.as_ref();
+            let mut task = match self.build_task(tag.clone(), uri) {
+                Ok(task) => task,
+                Err(error) => {
+                    failed.push((uri.to_owned(), error));
+                    continue;
+                }
+            };
+
+            if self.refused_by_loop_guard(task.uri()) {
+                continue;
+            }
+            self.stamp_referer(&mut task);
+            batch.push(task);
+        }
+
+        if let Some(graph) = &self.hooks.link_graph {
+            let edges = batch
+                .iter()
+                .map(|task| (self.uri.clone(), task.uri().clone()))
+                .collect();
+            graph.write_bulk(edges).await?;
+        }
+
+        if !batch.is_empty() {
+            for task in &batch {
+                self.publish_enqueued(task);
+            }
+            self.dataset.write_bulk(batch).await?;
+            self.hooks.wakeup.notify_one();
+        }
+
+        Ok(failed)
+    }
+
+    /// Enqueues a prepared [`Task`], e.g. a non-`GET` request assembled
+    /// through [`Task::builder`].
+    ///
+    /// The task inherits the crawling depth of the current request plus
+    /// one and records it as the [`RequestSource`], like the URL-based
+    /// appends; the URL normalizer does not apply to prepared requests.
+    ///
+    /// Returns whether the request was enqueued; see
+    /// [`RequestQueue::with_max_depth`].
+    pub async fn append_request(&self, mut task: Task) -> Result<bool> {
+        if self.exceeds_max_depth() {
+            tracing::trace!(uri = %task.uri(), "append refused by the depth cap");
+            return Ok(false);
+        }
+
+        if self.refused_by_loop_guard(task.uri()) {
+            return Ok(false);
+        }
+
+        let extensions = task.request_mut().extensions_mut();
+        extensions.insert(crate::context::Depth(self.depth + 1));
+        extensions.insert(RequestSource {
+            uri: self.uri.clone(),
+            tag: self.tag.clone(),
+        });
+        self.stamp_referer(&mut task);
+
+        if let Some(graph) = &self.hooks.link_graph {
+            graph.write((self.uri.clone(), task.uri().clone())).await?;
+        }
+
+        self.publish_enqueued(&task);
+        self.dataset.write(task).await?;
+        self.hooks.wakeup.notify_one();
+        Ok(true)
+    }
+
+    /// Publishes a [`CrawlEvent::RequestEnqueued`] for the given task.
+    fn publish_enqueued(&self, task: &Task) {
+        self.hooks.events.publish(|| CrawlEvent::RequestEnqueued {
+            tag: task.tag().clone(),
+            uri: task.uri().clone(),
+        });
+    }
+
+    /// Returns `true` if an appended request would exceed the depth cap.
+    fn exceeds_max_depth(&self) -> bool {
+        self.max_depth.is_some_and(|max_depth| self.depth + 1 > max_depth)
+    }
+
+    /// Sets the `Referer` header to the current request's URL when auto
+    /// referer is on and the task does not carry one already.
+    fn stamp_referer(&self, task: &mut Task) {
+        if !self.hooks.auto_referer {
+            return;
+        }
+
+        let headers = task.request_mut().headers_mut();
+        if headers.contains_key(http::header::REFERER) {
+            return;
+        }
+
+        match http::HeaderValue::from_str(&self.uri.to_string()) {
+            Ok(referer) => {
+                headers.insert(http::header::REFERER, referer);
+            }
+            Err(_) => tracing::debug!(uri = %self.uri, "source url is not a valid referer value"),
+        }
+    }
+
+    /// Returns `true` if the loop guard refuses the given URI.
+    fn refused_by_loop_guard(&self, uri: &Uri) -> bool {
+        self.hooks
+            .loop_guard
+            .as_ref()
+            .is_some_and(|guard| !guard.admit(uri))
+    }
+
+    /// Returns the crawling depth of the current request.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn build_task(&self, tag: Tag, uri: &str) -> Result<Task> {
+        let uri = match &self.hooks.normalizer {
+            // An unparsable URI passes through; `TaskBuilder::build`
+            // surfaces the parse error with the usual kind.
+            Some(normalize) => match uri.parse::<Uri>() {
+                Ok(parsed) => normalize(parsed).to_string(),
+                Err(_) => uri.to_owned(),
+            },
+            None => uri.to_owned(),
+        };
+
+        TaskBuilder::new(uri)
+            .with_tag(tag)
+            .with_depth(self.depth + 1)
+            .with_source(RequestSource {
+                uri: self.uri.clone(),
+                tag: self.tag.clone(),
+            })
+            .build()
+    }
+}
+
+impl std::fmt::Debug for RequestQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestQueue")
+            .field("tag", &self.tag)
+            .field("uri", &self.uri)
+            .field("depth", &self.depth)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::dataset::InMemDataset;
+
+    fn queue_with(hooks: QueueHooks) -> (RequestQueue, Data<Task>) {
+        let dataset = Data::new(InMemDataset::queue());
+        let uri: Uri = "https://example.com/".parse().unwrap();
+        let queue = RequestQueue::new(dataset.clone(), Tag::Fallback, uri, 0, hooks);
+        (queue, dataset)
+    }
+
+    #[test]
+    fn normalizer_sorts_query_params() {
+        let uri: Uri = "https://example.com/s?b=2&a=1".parse().unwrap();
+        assert_eq!(normalize_uri(uri).to_string(), "https://example.com/s?a=1&b=2");
+
+        let uri: Uri = "https://example.com/plain".parse().unwrap();
+        assert_eq!(normalize_uri(uri).to_string(), "https://example.com/plain");
+    }
+
+    #[tokio::test]
+    async fn append_applies_normalizer() {
+        let hooks = QueueHooks {
+            normalizer: Some(Arc::new(normalize_uri)),
+            ..QueueHooks::default()
+        };
+        let (queue, dataset) = queue_with(hooks);
+        queue.append("https://example.com/s?b=2&a=1").await.unwrap();
+
+        let task = dataset.read().await.unwrap().unwrap();
+        assert_eq!(task.uri().to_string(), "https://example.com/s?a=1&b=2");
+        assert_eq!(task.depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn append_request_keeps_method_and_stamps_depth() {
+        let (queue, dataset) = queue_with(QueueHooks::default());
+        let task = Task::builder("https://example.com/search")
+            .with_method(http::Method::POST)
+            .with_body("q=anvil")
+            .with_tag("results")
+            .build()
+            .unwrap();
+        queue.append_request(task).await.unwrap();
+
+        let task = dataset.read().await.unwrap().unwrap();
+        assert_eq!(task.request().method(), http::Method::POST);
+        assert_eq!(task.depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn append_many_batches_and_reports_failures() {
+        let (queue, dataset) = queue_with(QueueHooks::default());
+        let failed = queue
+            .append_many("page", [
+                "https://example.com/a",
+                "not a url",
+                "https://example.com/b",
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, "not a url");
+        assert_eq!(dataset.len().await, 2);
+
+        let task = dataset.read().await.unwrap().unwrap();
+        assert_eq!(*task.tag(), Tag::from("page"));
+        assert_eq!(task.depth(), 1);
+    }
+
+    #[tokio::test]
+    async fn depth_cap_refuses_deep_appends() {
+        let dataset = Data::new(InMemDataset::queue());
+        let uri: Uri = "https://example.com/".parse().unwrap();
+        let queue = RequestQueue::new(dataset.clone(), Tag::Fallback, uri, 1, QueueHooks::default())
+            .with_max_depth(1);
+
+        let appended = queue.append("https://example.com/deep").await.unwrap();
+        assert!(!appended);
+        assert_eq!(dataset.len().await, 0);
+
+        let (queue, dataset) = queue_with(QueueHooks::default());
+        let appended = queue
+            .with_max_depth(1)
+            .append("https://example.com/next")
+            .await
+            .unwrap();
+        assert!(appended);
+        assert_eq!(dataset.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn auto_referer_stamps_the_source_url() {
+        let hooks = QueueHooks {
+            auto_referer: true,
+            ..QueueHooks::default()
+        };
+        let (queue, dataset) = queue_with(hooks.clone());
+        queue.append("https://example.com/next").await.unwrap();
+
+        let task = dataset.read().await.unwrap().unwrap();
+        let referer = &task.request().headers()[http::header::REFERER];
+        assert_eq!(referer, "https://example.com/");
+
+        // An explicit referer wins over the automatic one.
+        let (queue, dataset) = queue_with(hooks);
+        let task = Task::builder("https://example.com/next").build().unwrap();
+        let mut task = task;
+        task.request_mut()
+            .headers_mut()
+            .insert(http::header::REFERER, "https://other.com/".parse().unwrap());
+        queue.append_request(task).await.unwrap();
+
+        let task = dataset.read().await.unwrap().unwrap();
+        assert_eq!(&task.request().headers()[http::header::REFERER], "https://other.com/");
+    }
+
+    #[tokio::test]
+    async fn loop_guard_throttles_recurring_url_shapes() {
+        let hooks = QueueHooks {
+            loop_guard: Some(Arc::new(LoopGuard::new(2))),
+            ..QueueHooks::default()
+        };
+        let (queue, dataset) = queue_with(hooks);
+
+        // Same shape: only the parameter values differ.
+        assert!(queue.append("https://example.com/cal?day=1").await.unwrap());
+        assert!(queue.append("https://example.com/cal?day=2").await.unwrap());
+        assert!(!queue.append("https://example.com/cal?day=3").await.unwrap());
+
+        // A different path is a different fingerprint.
+        assert!(queue.append("https://example.com/about").await.unwrap());
+        assert_eq!(dataset.len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn append_records_link_graph_edge() {
+        let graph = Data::new(InMemDataset::queue());
+        let hooks = QueueHooks {
+            link_graph: Some(graph.clone()),
+            ..QueueHooks::default()
+        };
+
+        let (queue, _dataset) = queue_with(hooks);
+        queue.append("https://example.com/next").await.unwrap();
+
+        let (from, to) = graph.read().await.unwrap().unwrap();
+        assert_eq!(from.to_string(), "https://example.com/");
+        assert_eq!(to.to_string(), "https://example.com/next");
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::context::RequestSource;
+
+/// Routing label attached to every queued [`Task`].
+///
+/// Tags decide which route of a [`Router`] handles the response. A task
+/// without an explicit tag carries [`Tag::Fallback`] and is dispatched to
+/// the router's fallback handler.
+///
+/// [`Task`]: crate::context::Task
+/// [`Router`]: crate::routing::Router
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Tag {
+    /// Matches the fallback route of a router.
+    #[default]
+    Fallback,
+    /// A user-provided routing label.
+    Custom(Arc<str>),
+}
+
+impl Tag {
+    /// Creates a custom tag from the given label.
+    pub fn new(label: impl AsRef<str>) -> Self {
+        Tag::Custom(Arc::from(label.as_ref()))
+    }
+
+    /// Returns the label of a custom tag.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::Fallback => None,
+            Tag::Custom(label) => Some(label),
+        }
+    }
+
+    /// Returns `true` if this is the [`Tag::Fallback`] tag.
+    pub fn is_fallback(&self) -> bool {
+        matches!(self, Tag::Fallback)
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(label: &str) -> Self {
+        Tag::new(label)
+    }
+}
+
+impl From<String> for Tag {
+    fn from(label: String) -> Self {
+        Tag::Custom(Arc::from(label))
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tag::Fallback => f.write_str("(fallback)"),
+            Tag::Custom(label) => f.write_str(label),
+        }
+    }
+}
+
+/// Predicate over queued tasks, used to target tasks an [`Error`] cancels.
+///
+/// Attached to an error via [`Error::with_query`], the runner drops queued
+/// tasks matching the query once the error surfaces. The constructors
+/// select on different task properties:
+///
+/// - [`TagQuery::exact`] matches the task's own tag — "cancel every
+///   remaining `product` request".
+/// - [`TagQuery::prefix`] matches custom tags by label prefix — "cancel
+///   everything under `shop/`" when tags follow a naming scheme.
+/// - [`TagQuery::owner`] matches the tag of the request that *enqueued*
+///   the task, regardless of the task's own tag — "cancel everything a
+///   `listing` page discovered". Owner information is only present on
+///   tasks enqueued through the [`RequestQueue`], so seed requests never
+///   match an owner query.
+///
+/// [`Error`]: crate::Error
+/// [`Error::with_query`]: crate::Error::with_query
+/// [`RequestQueue`]: crate::context::RequestQueue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagQuery {
+    kind: QueryKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum QueryKind {
+    /// Matches tasks carrying exactly this tag.
+    Exact(Tag),
+    /// Matches custom tags whose label starts with this prefix.
+    Prefix(Arc<str>),
+    /// Matches tasks discovered while handling a request with this tag.
+    Owner(Tag),
+}
+
+impl TagQuery {
+    /// Query matching tasks carrying exactly the given tag.
+    pub fn exact(tag: Tag) -> Self {
+        Self {
+            kind: QueryKind::Exact(tag),
+        }
+    }
+
+    /// Query matching custom tags whose label starts with the given
+    /// prefix.
+    ///
+    /// [`Tag::Fallback`] carries no label and never matches.
+    pub fn prefix(prefix: impl AsRef<str>) -> Self {
+        Self {
+            kind: QueryKind::Prefix(Arc::from(prefix.as_ref())),
+        }
+    }
+
+    /// Query matching tasks discovered while handling the given tag.
+    pub fn owner(tag: Tag) -> Self {
+        Self {
+            kind: QueryKind::Owner(tag),
+        }
+    }
+
+    /// Returns `true` if a task carrying the given tag matches.
+    ///
+    /// Owner queries select on where a task came from, not on its tag,
+    /// so they always return `false` here; the runner evaluates them
+    /// against the [`RequestSource`] recorded at enqueue time.
+    pub fn matches(&self, tag: &Tag) -> bool {
+        self.matches_task(tag, None)
+    }
+
+    /// Returns `true` if a task with the given tag and source matches.
+    pub(crate) fn matches_task(&self, tag: &Tag, source: Option<&RequestSource>) -> bool {
+        match &self.kind {
+            QueryKind::Exact(query) => query == tag,
+            QueryKind::Prefix(prefix) => {
+                tag.as_str().is_some_and(|label| label.starts_with(&**prefix))
+            }
+            QueryKind::Owner(query) => source.is_some_and(|source| &source.tag == query),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn custom_round_trip() {
+        let tag = Tag::from("product");
+        assert_eq!(tag.as_str(), Some("product"));
+        assert_eq!(tag.to_string(), "product");
+        assert!(!tag.is_fallback());
+    }
+
+    #[test]
+    fn fallback_is_default() {
+        assert!(Tag::default().is_fallback());
+        assert_eq!(Tag::default().as_str(), None);
+    }
+
+    #[test]
+    fn query_matches_exact_tag() {
+        let query = TagQuery::exact(Tag::from("product"));
+        assert!(query.matches(&Tag::from("product")));
+        assert!(!query.matches(&Tag::from("article")));
+    }
+
+    #[test]
+    fn query_matches_label_prefix() {
+        let query = TagQuery::prefix("shop/");
+        assert!(query.matches(&Tag::from("shop/product")));
+        assert!(!query.matches(&Tag::from("blog/article")));
+        assert!(!query.matches(&Tag::Fallback));
+    }
+
+    #[test]
+    fn query_matches_owner_source() {
+        let query = TagQuery::owner(Tag::from("listing"));
+        let source = RequestSource {
+            uri: "https://example.com/".parse().unwrap(),
+            tag: Tag::from("listing"),
+        };
+        assert!(query.matches_task(&Tag::from("product"), Some(&source)));
+        assert!(!query.matches_task(&Tag::from("product"), None));
+    }
+}
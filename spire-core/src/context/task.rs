@@ -0,0 +1,315 @@
+use std::time::SystemTime;
+
+use http::{Method, Uri};
+
+use crate::context::{Body, Request, Tag};
+use crate::{Error, ErrorKind};
+
+/// Crawling depth of a [`Task`], stored in its request extensions.
+///
+/// Seeds start at depth `0`; every request enqueued through a
+/// [`RequestQueue`] carries the depth of its originating request plus one.
+///
+/// [`RequestQueue`]: crate::context::RequestQueue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Depth(pub usize);
+
+/// Dispatch priority of a [`Task`], stored in its request extensions.
+///
+/// Higher values dispatch sooner. Priorities only take effect when the
+/// request queue is backed by a priority-aware dataset; the default FIFO
+/// [`InMemDataset::queue`] ignores them.
+///
+/// [`InMemDataset::queue`]: crate::dataset::InMemDataset::queue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(pub u8);
+
+/// Origin of a [`Task`], stored in its request extensions.
+///
+/// Tracks the request a task was discovered from, letting middleware and
+/// the runner attribute follow-up work to its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSource {
+    /// URI of the request that enqueued this task.
+    pub uri: Uri,
+    /// Tag of the request that enqueued this task.
+    pub tag: Tag,
+}
+
+/// Last known modification time of a [`Task`]'s URL, stored in its
+/// request extensions.
+///
+/// Stamped by the sitemap [`IncludeLayer`] from `<lastmod>` entries, so
+/// handlers can tell how fresh a page claims to be.
+///
+/// [`IncludeLayer`]: crate::layer::IncludeLayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lastmod(pub SystemTime);
+
+/// A single unit of crawling work: an outgoing request plus its routing [`Tag`].
+#[derive(Debug)]
+pub struct Task {
+    request: Request,
+    tag: Tag,
+}
+
+impl Task {
+    /// Creates a task from a prepared request, tagged with [`Tag::Fallback`].
+    pub fn new(request: Request) -> Self {
+        Self {
+            request,
+            tag: Tag::Fallback,
+        }
+    }
+
+    /// Starts building a task for the given URI.
+    pub fn builder(uri: impl AsRef<str>) -> TaskBuilder {
+        TaskBuilder::new(uri)
+    }
+
+    /// Replaces the routing tag of this task.
+    pub fn with_tag(mut self, tag: impl Into<Tag>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Returns the routing tag of this task.
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns the URI of the underlying request.
+    pub fn uri(&self) -> &Uri {
+        self.request.uri()
+    }
+
+    /// Returns the crawling depth recorded in the request extensions.
+    pub fn depth(&self) -> usize {
+        self.request
+            .extensions()
+            .get::<Depth>()
+            .copied()
+            .unwrap_or_default()
+            .0
+    }
+
+    /// Returns the dispatch priority recorded in the request extensions.
+    pub fn priority(&self) -> u8 {
+        self.request
+            .extensions()
+            .get::<Priority>()
+            .copied()
+            .unwrap_or_default()
+            .0
+    }
+
+    /// Returns the last modification time recorded in the request
+    /// extensions, if any.
+    pub fn lastmod(&self) -> Option<SystemTime> {
+        self.request.extensions().get::<Lastmod>().map(|lastmod| lastmod.0)
+    }
+
+    /// Returns a reference to the underlying request.
+    pub fn request(&self) -> &Request {
+        &self.request
+    }
+
+    /// Returns a mutable reference to the underlying request.
+    pub fn request_mut(&mut self) -> &mut Request {
+        &mut self.request
+    }
+
+    /// Splits the task into its routing tag and request.
+    pub fn into_parts(self) -> (Tag, Request) {
+        (self.tag, self.request)
+    }
+}
+
+/// Builder assembling a [`Task`] from its parts.
+///
+/// Tasks are plain `GET` requests with an empty body unless changed via
+/// [`TaskBuilder::with_method`] and [`TaskBuilder::with_body`].
+#[derive(Debug)]
+pub struct TaskBuilder {
+    uri: String,
+    method: Method,
+    body: Body,
+    json: bool,
+    error: Option<Error>,
+    tag: Tag,
+    depth: usize,
+    priority: u8,
+    source: Option<RequestSource>,
+}
+
+impl TaskBuilder {
+    /// Starts building a task for the given URI.
+    pub fn new(uri: impl AsRef<str>) -> Self {
+        Self {
+            uri: uri.as_ref().to_owned(),
+            method: Method::GET,
+            body: Body::empty(),
+            json: false,
+            error: None,
+            tag: Tag::Fallback,
+            depth: 0,
+            priority: 0,
+            source: None,
+        }
+    }
+
+    /// Sets the HTTP method of the request.
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Sets the request body.
+    pub fn with_body(mut self, body: impl Into<Body>) -> Self {
+        self.body = body.into();
+        self.json = false;
+        self
+    }
+
+    /// Serializes the given value to JSON as the request body and sets
+    /// `Content-Type: application/json`.
+    ///
+    /// The value is serialized right away; a failure surfaces as an error
+    /// from [`TaskBuilder::build`]. Pairs naturally with the `Json`
+    /// response extractor for JSON-RPC or GraphQL endpoints.
+    pub fn with_json_body<T: serde::Serialize>(mut self, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(bytes) => {
+                self.body = Body::new(bytes);
+                self.json = true;
+            }
+            Err(error) => self.error = Some(Error::new(ErrorKind::Http, error)),
+        }
+
+        self
+    }
+
+    /// Sets the routing tag of the task.
+    pub fn with_tag(mut self, tag: impl Into<Tag>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// Sets the dispatch priority of the task; higher dispatches sooner.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the crawling depth recorded on the task.
+    pub(crate) fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Records the request this task was discovered from.
+    pub(crate) fn with_source(mut self, source: RequestSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Builds the task, validating the URI.
+    pub fn build(self) -> Result<Task, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let uri: Uri = self
+            .uri
+            .parse()
+            .map_err(|error| Error::new(ErrorKind::Http, format!("invalid uri: {error}")))?;
+        let mut builder = http::Request::builder().method(self.method).uri(uri);
+        if self.json {
+            builder = builder.header(http::header::CONTENT_TYPE, "application/json");
+        }
+        let mut request = builder
+            .body(self.body)
+            .map_err(|error| Error::new(ErrorKind::Http, error))?;
+
+        request.extensions_mut().insert(Depth(self.depth));
+        request.extensions_mut().insert(Priority(self.priority));
+        if let Some(source) = self.source {
+            request.extensions_mut().insert(source);
+        }
+
+        Ok(Task {
+            request,
+            tag: self.tag,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn builder_defaults() {
+        let task = Task::builder("https://example.com/")
+            .with_tag("product")
+            .build()
+            .unwrap();
+        assert_eq!(task.tag(), &Tag::from("product"));
+        assert_eq!(task.depth(), 0);
+        assert_eq!(task.priority(), 0);
+        assert_eq!(task.uri().host(), Some("example.com"));
+    }
+
+    #[test]
+    fn builder_records_priority() {
+        let task = Task::builder("https://example.com/")
+            .with_priority(200)
+            .build()
+            .unwrap();
+        assert_eq!(task.priority(), 200);
+        assert_eq!(task.request().extensions().get(), Some(&Priority(200)));
+    }
+
+    #[test]
+    fn builder_carries_method_and_body() {
+        let task = Task::builder("https://example.com/search")
+            .with_method(Method::POST)
+            .with_body("q=anvil")
+            .build()
+            .unwrap();
+        assert_eq!(task.request().method(), Method::POST);
+        assert_eq!(task.request().body().bytes().as_ref(), b"q=anvil");
+    }
+
+    #[test]
+    fn builder_serializes_json_bodies() {
+        let task = Task::builder("https://example.com/rpc")
+            .with_method(Method::POST)
+            .with_json_body(&serde_json::json!({ "query": "anvil" }))
+            .build()
+            .unwrap();
+
+        let content_type = &task.request().headers()[http::header::CONTENT_TYPE];
+        assert_eq!(content_type, "application/json");
+        assert_eq!(task.request().body().bytes().as_ref(), br#"{"query":"anvil"}"#);
+    }
+
+    #[test]
+    fn builder_surfaces_serialization_errors() {
+        use std::collections::HashMap;
+
+        // Maps without string keys cannot serialize to JSON.
+        let bad: HashMap<Vec<i32>, i32> = HashMap::from([(vec![1], 1)]);
+        let error = Task::builder("https://example.com/rpc")
+            .with_json_body(&bad)
+            .build()
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Http);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_uri() {
+        let error = Task::builder("not a uri").build().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Http);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::dataset::Dataset;
+use crate::Result;
+
+/// A type-erased [`Dataset`].
+pub struct BoxDataset<T> {
+    inner: Box<dyn Dataset<T>>,
+}
+
+impl<T: Send + 'static> BoxDataset<T> {
+    /// Erases the type of the given dataset.
+    pub fn new<D>(dataset: D) -> Self
+    where
+        D: Dataset<T>,
+    {
+        Self {
+            inner: Box::new(dataset),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Dataset<T> for BoxDataset<T> {
+    async fn write(&self, data: T) -> Result<()> {
+        self.inner.write(data).await
+    }
+
+    async fn read(&self) -> Result<Option<T>> {
+        self.inner.read().await
+    }
+
+    async fn len(&self) -> usize {
+        self.inner.len().await
+    }
+}
+
+impl<T> fmt::Debug for BoxDataset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxDataset").finish_non_exhaustive()
+    }
+}
+
+/// A type-erased, cloneable [`Dataset`].
+pub struct BoxCloneDataset<T> {
+    inner: Box<dyn CloneDataset<T>>,
+}
+
+impl<T: Send + 'static> BoxCloneDataset<T> {
+    /// Erases the type of the given cloneable dataset.
+    pub fn new<D>(dataset: D) -> Self
+    where
+        D: Dataset<T> + Clone,
+    {
+        Self {
+            inner: Box::new(dataset),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Dataset<T> for BoxCloneDataset<T> {
+    async fn write(&self, data: T) -> Result<()> {
+        self.inner.write(data).await
+    }
+
+    async fn read(&self) -> Result<Option<T>> {
+        self.inner.read().await
+    }
+
+    async fn len(&self) -> usize {
+        self.inner.len().await
+    }
+}
+
+impl<T: Send + 'static> Clone for BoxCloneDataset<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone_box(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for BoxCloneDataset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxCloneDataset").finish_non_exhaustive()
+    }
+}
+
+trait CloneDataset<T>: Dataset<T>
+where
+    T: Send,
+{
+    fn clone_box(&self) -> Box<dyn CloneDataset<T>>;
+}
+
+impl<T, D> CloneDataset<T> for D
+where
+    T: Send + 'static,
+    D: Dataset<T> + Clone,
+{
+    fn clone_box(&self) -> Box<dyn CloneDataset<T>> {
+        Box::new(self.clone())
+    }
+}
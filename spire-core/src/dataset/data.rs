@@ -0,0 +1,707 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::hash::Hash;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use async_trait::async_trait;
+use futures_core::Stream;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Sink, StreamExt, TryStreamExt};
+
+use crate::dataset::{BoxCloneDataset, Dataset, DatasetBulkExt};
+use crate::{Error, ErrorKind, Result};
+
+/// Output layout of [`Data::export_json`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A pretty-printed JSON array.
+    #[default]
+    Array,
+    /// One JSON document per line (JSONL).
+    Jsonl,
+}
+
+/// Cloneable, typed handle to a registered [`Dataset`].
+///
+/// `Data<T>` is how handlers interact with storage: it is extractable from
+/// a [`Context`] and resolves through the crawl-wide [`DatasetRegistry`].
+///
+/// [`Context`]: crate::context::Context
+/// [`DatasetRegistry`]: crate::dataset::DatasetRegistry
+pub struct Data<T> {
+    inner: BoxCloneDataset<T>,
+}
+
+impl<T: Send + 'static> Data<T> {
+    /// Wraps the given dataset into a typed handle.
+    pub fn new<D>(dataset: D) -> Self
+    where
+        D: Dataset<T> + Clone,
+    {
+        Self {
+            inner: BoxCloneDataset::new(dataset),
+        }
+    }
+
+    /// Stores a single value.
+    pub async fn write(&self, data: T) -> Result<()> {
+        self.inner.write(data).await
+    }
+
+    /// Removes and returns a stored value, or `None` if empty.
+    pub async fn read(&self) -> Result<Option<T>> {
+        self.inner.read().await
+    }
+
+    /// Drains the dataset into a freshly allocated vector.
+    pub async fn read_all(&self) -> Result<Vec<T>> {
+        let mut data = Vec::new();
+        self.read_all_into(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Drains the dataset into the given buffer, reusing its allocation.
+    ///
+    /// The buffer is cleared first; on return it holds every value the
+    /// dataset contained.
+    pub async fn read_all_into(&self, buf: &mut Vec<T>) -> Result<()> {
+        buf.clear();
+        while let Some(item) = self.inner.read().await? {
+            buf.push(item);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of currently stored values.
+    pub async fn len(&self) -> usize {
+        self.inner.len().await
+    }
+
+    /// Returns `true` if no values are currently stored.
+    pub async fn is_empty(&self) -> bool {
+        self.inner.is_empty().await
+    }
+
+    /// Returns a stream draining the dataset until the first empty read.
+    pub fn stream(&self) -> DataStream<T> {
+        let stream = stream::unfold(self.clone(), |data| async move {
+            match data.read().await {
+                Ok(Some(item)) => Some((Ok(item), data)),
+                Ok(None) => None,
+                Err(error) => Some((Err(error), data)),
+            }
+        });
+
+        DataStream {
+            inner: stream.boxed(),
+        }
+    }
+
+    /// Returns a sink writing each sent value into the dataset.
+    ///
+    /// The [`Sink`] counterpart of [`Data::stream`]: together they make a
+    /// dataset usable wherever `futures` adapters (`send_all`,
+    /// `forward`, `select!`) expect the channel traits instead of
+    /// dataset methods.
+    pub fn sink(&self) -> DataSink<T> {
+        DataSink {
+            data: self.clone(),
+            pending: None,
+        }
+    }
+
+    /// Returns a guard buffering writes into a single bulk flush.
+    ///
+    /// A handler inserting many records one [`Data::write`] at a time
+    /// pays the per-write cost of the backing store — for a DB-backed
+    /// dataset, one transaction per record. The batch buffers the records
+    /// in memory and stores them all at once through
+    /// [`DatasetBulkExt::write_bulk`] when committed. See [`DataBatch`]
+    /// for the drop semantics of an un-committed batch.
+    pub fn batch(&self) -> DataBatch<T> {
+        DataBatch {
+            data: self.clone(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Drains the dataset into a JSON file at the given path.
+    ///
+    /// The usual last step of a crawl: grab the handle before the crawl
+    /// starts (or keep a clone of the dataset) and export once
+    /// [`Client::run`] returns. [`ExportFormat::Array`] writes one
+    /// pretty-printed array, [`ExportFormat::Jsonl`] one document per
+    /// line. Returns the number of exported records.
+    ///
+    /// [`Client::run`]: crate::client::Client::run
+    pub async fn export_json(&self, path: impl AsRef<Path>, format: ExportFormat) -> Result<usize>
+    where
+        T: serde::Serialize,
+    {
+        let items = self.read_all().await?;
+        let json = |error: serde_json::Error| Error::new(ErrorKind::Context, error);
+
+        let mut buf = Vec::new();
+        match format {
+            ExportFormat::Array => serde_json::to_writer_pretty(&mut buf, &items).map_err(json)?,
+            ExportFormat::Jsonl => {
+                for item in &items {
+                    serde_json::to_writer(&mut buf, item).map_err(json)?;
+                    buf.push(b'\n');
+                }
+            }
+        }
+
+        tokio::fs::write(path, buf)
+            .await
+            .map_err(|error| Error::new(ErrorKind::Context, error))?;
+        Ok(items.len())
+    }
+
+    /// Drains the dataset, tallying values by the key `key_fn` derives.
+    ///
+    /// The usual first step of post-processing — counts per category,
+    /// per host, per status — without hand-rolling a loop over
+    /// [`Data::read_all`]. Use [`Data::group_by`] to keep the values.
+    pub async fn count_by<K, F>(&self, mut key_fn: F) -> Result<HashMap<K, usize>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&T) -> K,
+    {
+        let mut counts = HashMap::new();
+        while let Some(item) = self.inner.read().await? {
+            *counts.entry(key_fn(&item)).or_insert(0) += 1;
+        }
+
+        Ok(counts)
+    }
+
+    /// Drains the dataset, grouping values by the key `key_fn` derives.
+    ///
+    /// Values keep their read order within each group. See
+    /// [`Data::count_by`] when only the tallies are needed.
+    pub async fn group_by<K, F>(&self, mut key_fn: F) -> Result<HashMap<K, Vec<T>>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+        while let Some(item) = self.inner.read().await? {
+            groups.entry(key_fn(&item)).or_default().push(item);
+        }
+
+        Ok(groups)
+    }
+
+    /// Drains the dataset, running `handler` on each value with at most
+    /// `limit` invocations in flight.
+    ///
+    /// Reads overlap with the handlers, so this is the preferred way to
+    /// post-process a crawl (uploads, inserts) without serializing on each
+    /// item. A `limit` of zero means unbounded concurrency. Stops at the
+    /// first error, after the already running handlers settle.
+    pub async fn process_concurrent<F, Fut>(&self, limit: usize, handler: F) -> Result<()>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let limit = (limit > 0).then_some(limit);
+        self.stream()
+            .try_for_each_concurrent(limit, handler)
+            .await
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Dataset<T> for Data<T> {
+    async fn write(&self, data: T) -> Result<()> {
+        self.inner.write(data).await
+    }
+
+    async fn read(&self) -> Result<Option<T>> {
+        self.inner.read().await
+    }
+
+    async fn len(&self) -> usize {
+        self.inner.len().await
+    }
+}
+
+impl<T: Send + 'static> Clone for Data<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Data<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Data").finish_non_exhaustive()
+    }
+}
+
+/// Stream draining a [`Data`] handle until the first empty read.
+///
+/// Note that the stream ends when the underlying dataset reports empty,
+/// which is not necessarily the end of the crawl. The usual
+/// [`StreamExt`]/[`TryStreamExt`] adapters apply; see
+/// [`Data::process_concurrent`] for the common bounded-concurrency drain.
+///
+/// [`StreamExt`]: futures_util::StreamExt
+/// [`TryStreamExt`]: futures_util::TryStreamExt
+pub struct DataStream<T> {
+    inner: BoxStream<'static, Result<T>>,
+}
+
+impl<T> DataStream<T> {
+    /// Removes and returns the next value, or `None` once the dataset
+    /// reports empty.
+    ///
+    /// The inherent mirror of [`TryStreamExt::try_next`]: flipping the
+    /// nested `Option<Result<T>>` keeps "stream ended" (`Ok(None)`)
+    /// distinct from "read failed" (`Err`) without importing the trait.
+    pub async fn try_next(&mut self) -> Result<Option<T>> {
+        self.inner.next().await.transpose()
+    }
+
+    /// Drains the stream, running `handler` on each value in order.
+    ///
+    /// Stops at the first error, whether it came from a read or from the
+    /// handler, and returns it. See [`Data::process_concurrent`] for the
+    /// overlapping-reads variant.
+    pub async fn try_for_each<F, Fut>(mut self, mut handler: F) -> Result<()>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        while let Some(item) = self.try_next().await? {
+            handler(item).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Drains the stream, running `handler` on each successfully read
+    /// value and skipping failed reads with a debug-level trace.
+    ///
+    /// The lenient counterpart of [`DataStream::try_for_each`] for
+    /// datasets whose read errors are recoverable (e.g. a malformed row
+    /// in persistent storage). Handler errors still stop the drain.
+    pub async fn for_each_ok<F, Fut>(mut self, mut handler: F) -> Result<()>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        while let Some(item) = self.inner.next().await {
+            match item {
+                Ok(item) => handler(item).await?,
+                Err(error) => tracing::debug!(%error, "skipping failed dataset read"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The in-flight write driven by a [`DataSink`].
+type SinkFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// Sink writing sent values into a [`Data`] handle, returned by
+/// [`Data::sink`].
+///
+/// One write is in flight at a time; `poll_ready` resolves once the
+/// previous write settled. Errors surface from `poll_flush`/`poll_ready`
+/// like on any other sink. Closing the sink only flushes — a dataset has
+/// no end-of-stream notion to propagate.
+pub struct DataSink<T> {
+    data: Data<T>,
+    pending: Option<SinkFuture>,
+}
+
+impl<T: Send + 'static> DataSink<T> {
+    /// Drives the in-flight write, if any, to completion.
+    fn poll_pending(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        let Some(pending) = self.pending.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let result = std::task::ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+        Poll::Ready(result)
+    }
+}
+
+impl<T: Send + 'static> Sink<T> for DataSink<T> {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        self.poll_pending(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<()> {
+        debug_assert!(self.pending.is_none(), "start_send before poll_ready");
+        let data = self.data.clone();
+        self.pending = Some(Box::pin(async move { data.write(item).await }));
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        self.poll_pending(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        self.poll_pending(cx)
+    }
+}
+
+impl<T> fmt::Debug for DataSink<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataSink").finish_non_exhaustive()
+    }
+}
+
+/// Buffered writes to a [`Data`] handle, returned by [`Data::batch`].
+///
+/// Records added with [`DataBatch::push`] stay in memory until
+/// [`DataBatch::commit`] stores them all through one
+/// [`DatasetBulkExt::write_bulk`] call. Dropping an un-committed batch
+/// still flushes it — as a spawned background task — but any flush error
+/// is only logged, and outside a tokio runtime the records are lost.
+/// Call `commit` whenever the write outcome matters.
+pub struct DataBatch<T: Send + 'static> {
+    data: Data<T>,
+    buf: Vec<T>,
+}
+
+impl<T: Send + 'static> DataBatch<T> {
+    /// Adds a record to the batch without touching the dataset.
+    pub fn push(&mut self, item: T) {
+        self.buf.push(item);
+    }
+
+    /// Returns the number of buffered records.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if no records are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Discards the buffered records without writing them.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Stores every buffered record as one bulk write.
+    pub async fn commit(mut self) -> Result<()> {
+        let items = std::mem::take(&mut self.buf);
+        self.data.write_bulk(items).await
+    }
+}
+
+impl<T: Send + 'static> Drop for DataBatch<T> {
+    fn drop(&mut self) {
+        if self.buf.is_empty() {
+            return;
+        }
+
+        let items = std::mem::take(&mut self.buf);
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let data = self.data.clone();
+                handle.spawn(async move {
+                    if let Err(error) = data.write_bulk(items).await {
+                        tracing::warn!(%error, "flushing a dropped batch failed");
+                    }
+                });
+            }
+            Err(_) => {
+                tracing::warn!(records = items.len(), "dropped batch outside a runtime; records lost");
+            }
+        }
+    }
+}
+
+impl<T: Send + 'static> fmt::Debug for DataBatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataBatch")
+            .field("len", &self.buf.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for DataStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl<T> fmt::Debug for DataStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataStream").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures_util::TryStreamExt;
+
+    use super::*;
+    use crate::dataset::InMemDataset;
+
+    #[tokio::test]
+    async fn read_all_drains() {
+        let data = Data::new(InMemDataset::queue());
+        data.write(1).await.unwrap();
+        data.write(2).await.unwrap();
+
+        assert_eq!(data.read_all().await.unwrap(), vec![1, 2]);
+        assert!(data.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn read_all_into_reuses_buffer() {
+        let data = Data::new(InMemDataset::queue());
+        let mut buf = vec![0; 8];
+
+        data.write(1).await.unwrap();
+        data.read_all_into(&mut buf).await.unwrap();
+        assert_eq!(buf, vec![1]);
+
+        data.write(2).await.unwrap();
+        data.read_all_into(&mut buf).await.unwrap();
+        assert_eq!(buf, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn export_json_writes_both_layouts() {
+        let dir = std::env::temp_dir();
+        let array_path = dir.join(format!("spire-export-{}.json", std::process::id()));
+        let jsonl_path = dir.join(format!("spire-export-{}.jsonl", std::process::id()));
+
+        let data = Data::new(InMemDataset::queue());
+        for item in [1, 2, 3] {
+            data.write(item).await.unwrap();
+        }
+        assert_eq!(data.export_json(&array_path, ExportFormat::Array).await.unwrap(), 3);
+
+        let array = std::fs::read_to_string(&array_path).unwrap();
+        let parsed: Vec<i32> = serde_json::from_str(&array).unwrap();
+        assert_eq!(parsed, vec![1, 2, 3]);
+
+        for item in [4, 5] {
+            data.write(item).await.unwrap();
+        }
+        assert_eq!(data.export_json(&jsonl_path, ExportFormat::Jsonl).await.unwrap(), 2);
+
+        let jsonl = std::fs::read_to_string(&jsonl_path).unwrap();
+        assert_eq!(jsonl, "4\n5\n");
+
+        std::fs::remove_file(array_path).ok();
+        std::fs::remove_file(jsonl_path).ok();
+    }
+
+    #[tokio::test]
+    async fn count_by_tallies_per_key() {
+        let data = Data::new(InMemDataset::queue());
+        for item in ["a", "b", "a", "a"] {
+            data.write(item.to_owned()).await.unwrap();
+        }
+
+        let counts = data.count_by(|item| item.clone()).await.unwrap();
+        assert_eq!(counts.get("a"), Some(&3));
+        assert_eq!(counts.get("b"), Some(&1));
+        assert!(data.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn group_by_keeps_read_order_within_groups() {
+        let data = Data::new(InMemDataset::queue());
+        for item in [1, 2, 3, 4, 5] {
+            data.write(item).await.unwrap();
+        }
+
+        let groups = data.group_by(|item| item % 2).await.unwrap();
+        assert_eq!(groups.get(&0), Some(&vec![2, 4]));
+        assert_eq!(groups.get(&1), Some(&vec![1, 3, 5]));
+    }
+
+    #[tokio::test]
+    async fn process_concurrent_drains() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let data = Data::new(InMemDataset::queue());
+        for item in 0..16 {
+            data.write(item).await.unwrap();
+        }
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        data.process_concurrent(4, |_item| {
+            let seen = seen.clone();
+            async move {
+                tokio::task::yield_now().await;
+                seen.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(seen.load(Ordering::Relaxed), 16);
+        assert!(data.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn sink_writes_into_the_dataset() {
+        use futures_util::SinkExt;
+
+        let data = Data::new(InMemDataset::queue());
+        let mut sink = data.sink();
+        for item in [1, 2] {
+            sink.send(item).await.unwrap();
+        }
+        sink.close().await.unwrap();
+
+        assert_eq!(data.read_all().await.unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn batch_commit_stores_all_records() {
+        let data = Data::new(InMemDataset::queue());
+        let mut batch = data.batch();
+        for item in [1, 2, 3] {
+            batch.push(item);
+        }
+
+        assert_eq!(batch.len(), 3);
+        assert!(data.is_empty().await);
+        batch.commit().await.unwrap();
+        assert_eq!(data.read_all().await.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn dropped_batch_flushes_in_the_background() {
+        let data = Data::new(InMemDataset::queue());
+        let mut batch = data.batch();
+        batch.push(1);
+        drop(batch);
+
+        // The flush runs as a spawned task; give it a chance to settle.
+        while data.is_empty().await {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(data.read_all().await.unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn cleared_batch_writes_nothing() {
+        let data = Data::new(InMemDataset::queue());
+        let mut batch = data.batch();
+        batch.push(1);
+        batch.clear();
+        assert!(batch.is_empty());
+
+        drop(batch);
+        tokio::task::yield_now().await;
+        assert!(data.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn stream_yields_until_empty() {
+        let data = Data::new(InMemDataset::queue());
+        for item in ["a", "b"] {
+            data.write(item.to_owned()).await.unwrap();
+        }
+
+        let items: Vec<_> = data.stream().try_collect().await.unwrap();
+        assert_eq!(items, vec!["a".to_owned(), "b".to_owned()]);
+    }
+
+    /// Dataset failing every odd-numbered read.
+    #[derive(Debug, Clone, Default)]
+    struct Flaky {
+        inner: InMemDataset<i32>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Dataset<i32> for Flaky {
+        async fn write(&self, data: i32) -> Result<()> {
+            self.inner.write(data).await
+        }
+
+        async fn read(&self) -> Result<Option<i32>> {
+            match self.reads.fetch_add(1, Ordering::Relaxed) % 2 {
+                0 => self.inner.read().await,
+                _ => Err(crate::Error::msg(crate::ErrorKind::Context, "flaky read")),
+            }
+        }
+
+        async fn len(&self) -> usize {
+            self.inner.len().await
+        }
+    }
+
+    #[tokio::test]
+    async fn try_next_distinguishes_end_from_error() {
+        let data = Data::new(Flaky::default());
+        data.write(1).await.unwrap();
+
+        let mut stream = data.stream();
+        assert_eq!(stream.try_next().await.unwrap(), Some(1));
+        assert!(stream.try_next().await.is_err());
+        assert_eq!(stream.try_next().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn try_for_each_stops_at_the_first_error() {
+        let data = Data::new(Flaky::default());
+        for item in [1, 2] {
+            data.write(item).await.unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let result = data
+            .stream()
+            .try_for_each(|item| {
+                seen.push(item);
+                async { Ok(()) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(seen, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn for_each_ok_skips_failed_reads() {
+        let data = Data::new(Flaky::default());
+        for item in [1, 2] {
+            data.write(item).await.unwrap();
+        }
+
+        let mut seen = Vec::new();
+        data.stream()
+            .for_each_ok(|item| {
+                seen.push(item);
+                async { Ok(()) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(seen, vec![1, 2]);
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::dataset::Dataset;
+use crate::Result;
+
+/// Upper bounds (in microseconds) of the latency histogram buckets; the
+/// last bucket is unbounded.
+const BUCKET_BOUNDS: [u64; 5] = [100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// Dataset adapter recording operation counts and latencies.
+///
+/// Created by [`DatasetExt::metered`]. Every `write` and `read` is timed
+/// and tallied; [`Metered::stats`] returns a snapshot at any point, also
+/// from a clone held outside the crawl. Most useful around datasets with
+/// real I/O behind them, where read latency is the first thing to check
+/// when a crawl slows down.
+///
+/// Recording is a few atomic operations per call — cheap enough to leave
+/// on in production.
+///
+/// [`DatasetExt::metered`]: crate::dataset::DatasetExt::metered
+#[derive(Debug, Clone)]
+pub struct Metered<D> {
+    inner: D,
+    metrics: Arc<Metrics>,
+}
+
+#[derive(Debug)]
+struct Metrics {
+    name: String,
+    writes: OpMetrics,
+    reads: OpMetrics,
+}
+
+#[derive(Debug, Default)]
+struct OpMetrics {
+    count: AtomicU64,
+    total_micros: AtomicU64,
+    max_micros: AtomicU64,
+    buckets: [AtomicU64; 6],
+}
+
+impl OpMetrics {
+    fn record(&self, elapsed: Duration) {
+        let micros = elapsed.as_micros() as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_micros.fetch_add(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|bound| micros < *bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> OpStats {
+        let count = self.count.load(Ordering::Relaxed);
+        let total = Duration::from_micros(self.total_micros.load(Ordering::Relaxed));
+
+        OpStats {
+            count,
+            mean_latency: total.checked_div(count as u32).unwrap_or_default(),
+            max_latency: Duration::from_micros(self.max_micros.load(Ordering::Relaxed)),
+            latency_buckets: std::array::from_fn(|index| {
+                self.buckets[index].load(Ordering::Relaxed)
+            }),
+        }
+    }
+}
+
+/// Counters and latencies of one operation kind of a [`Metered`] dataset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpStats {
+    /// Number of operations recorded.
+    pub count: u64,
+    /// Mean latency over all recorded operations.
+    pub mean_latency: Duration,
+    /// Largest latency recorded.
+    pub max_latency: Duration,
+    /// Log-scale latency histogram: counts of operations faster than
+    /// 100µs, 1ms, 10ms, 100ms and 1s, plus everything slower.
+    pub latency_buckets: [u64; 6],
+}
+
+/// Snapshot of the counters of a [`Metered`] dataset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetStats {
+    /// The name given to [`DatasetExt::metered`].
+    ///
+    /// [`DatasetExt::metered`]: crate::dataset::DatasetExt::metered
+    pub name: String,
+    /// Write counters and latencies.
+    pub writes: OpStats,
+    /// Read counters and latencies.
+    pub reads: OpStats,
+}
+
+impl<D> Metered<D> {
+    pub(crate) fn new(inner: D, name: impl Into<String>) -> Self {
+        Self {
+            inner,
+            metrics: Arc::new(Metrics {
+                name: name.into(),
+                writes: OpMetrics::default(),
+                reads: OpMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns a snapshot of the recorded counters.
+    pub fn stats(&self) -> DatasetStats {
+        DatasetStats {
+            name: self.metrics.name.clone(),
+            writes: self.metrics.writes.snapshot(),
+            reads: self.metrics.reads.snapshot(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T, D> Dataset<T> for Metered<D>
+where
+    T: Send + 'static,
+    D: Dataset<T>,
+{
+    async fn write(&self, data: T) -> Result<()> {
+        let started = Instant::now();
+        let result = self.inner.write(data).await;
+        self.metrics.writes.record(started.elapsed());
+        result
+    }
+
+    async fn read(&self) -> Result<Option<T>> {
+        let started = Instant::now();
+        let result = self.inner.read().await;
+        self.metrics.reads.record(started.elapsed());
+        result
+    }
+
+    async fn len(&self) -> usize {
+        self.inner.len().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::{DatasetExt, InMemDataset};
+
+    #[tokio::test]
+    async fn counters_track_operations() {
+        let metered = InMemDataset::<u32>::queue().metered("records");
+        metered.write(1).await.unwrap();
+        metered.write(2).await.unwrap();
+        metered.read().await.unwrap();
+
+        let stats = metered.stats();
+        assert_eq!(stats.name, "records");
+        assert_eq!(stats.writes.count, 2);
+        assert_eq!(stats.reads.count, 1);
+        assert!(stats.writes.max_latency >= stats.writes.mean_latency);
+
+        // Every operation lands in exactly one histogram bucket.
+        let writes: u64 = stats.writes.latency_buckets.iter().sum();
+        assert_eq!(writes, 2);
+    }
+
+    #[tokio::test]
+    async fn clones_share_the_counters() {
+        let metered = InMemDataset::<u32>::queue().metered("shared");
+        let observer = metered.clone();
+
+        metered.write(7).await.unwrap();
+        assert_eq!(observer.stats().writes.count, 1);
+    }
+}
@@ -0,0 +1,143 @@
+//! Storage abstraction for request queues and scraped records.
+
+use async_trait::async_trait;
+
+use crate::Result;
+
+mod boxed;
+mod data;
+mod memory;
+mod metered;
+mod registry;
+mod tee;
+
+pub use boxed::{BoxCloneDataset, BoxDataset};
+pub use data::{Data, DataBatch, DataSink, DataStream, ExportFormat};
+pub use memory::InMemDataset;
+pub use metered::{DatasetStats, Metered, OpStats};
+pub use registry::DatasetRegistry;
+pub use tee::Tee;
+
+/// Asynchronous storage for values of type `T`.
+///
+/// A dataset is both a sink and a source: the runner stores its request
+/// queue in one, and handlers persist scraped records through them. The
+/// read order (FIFO vs LIFO) is an implementation choice; see
+/// [`InMemDataset::queue`] and [`InMemDataset::stack`].
+#[async_trait]
+pub trait Dataset<T>: Send + Sync + 'static
+where
+    T: Send,
+{
+    /// Stores a single value.
+    async fn write(&self, data: T) -> Result<()>;
+
+    /// Removes and returns a stored value, or `None` if empty.
+    async fn read(&self) -> Result<Option<T>>;
+
+    /// Returns the number of currently stored values.
+    async fn len(&self) -> usize;
+
+    /// Returns `true` if no values are currently stored.
+    async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+/// Adapters available on every sized [`Dataset`].
+pub trait DatasetExt<T>: Dataset<T> + Sized
+where
+    T: Send + 'static,
+{
+    /// Erases the dataset type behind a [`BoxDataset`].
+    fn boxed(self) -> BoxDataset<T> {
+        BoxDataset::new(self)
+    }
+
+    /// Erases the dataset type behind a cloneable [`BoxCloneDataset`].
+    fn boxed_clone(self) -> BoxCloneDataset<T>
+    where
+        Self: Clone,
+    {
+        BoxCloneDataset::new(self)
+    }
+
+    /// Duplicates every write into `secondary`, reading from `self` only.
+    fn tee<B>(self, secondary: B) -> Tee<Self, B>
+    where
+        B: Dataset<T>,
+    {
+        Tee::new(self, secondary)
+    }
+
+    /// Records operation counts and latencies under the given name.
+    ///
+    /// See [`Metered::stats`] for the snapshot this exposes.
+    fn metered(self, name: impl Into<String>) -> Metered<Self> {
+        Metered::new(self, name)
+    }
+}
+
+impl<T, D> DatasetExt<T> for D
+where
+    T: Send + 'static,
+    D: Dataset<T>,
+{
+}
+
+/// Bulk read/write helpers over a [`Dataset`].
+///
+/// The default implementations loop over [`Dataset::write`] and
+/// [`Dataset::read`]; storage backends with a cheaper batched path should
+/// override them.
+#[async_trait]
+pub trait DatasetBulkExt<T>: Dataset<T>
+where
+    T: Send + 'static,
+{
+    /// Stores every value of the given batch.
+    async fn write_bulk(&self, data: Vec<T>) -> Result<()> {
+        for item in data {
+            self.write(item).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns up to `limit` stored values.
+    async fn read_bulk(&self, limit: usize) -> Result<Vec<T>> {
+        let mut data = Vec::new();
+        while data.len() < limit {
+            match self.read().await? {
+                Some(item) => data.push(item),
+                None => break,
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Drains the dataset into the given buffer, reusing its allocation.
+    ///
+    /// The buffer is cleared first; on return it holds every value the
+    /// dataset contained. Prefer this over [`Data::read_all`] in loops that
+    /// would otherwise allocate a fresh vector per iteration.
+    ///
+    /// [`Data::read_all`]: crate::dataset::Data::read_all
+    async fn read_all_into(&self, buf: &mut Vec<T>) -> Result<()> {
+        buf.clear();
+        while let Some(item) = self.read().await? {
+            buf.push(item);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T, D> DatasetBulkExt<T> for D
+where
+    T: Send + 'static,
+    D: Dataset<T> + ?Sized,
+{
+}
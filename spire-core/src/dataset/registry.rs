@@ -0,0 +1,232 @@
+use std::any::{self, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::context::Tag;
+use crate::dataset::{Data, Dataset, InMemDataset};
+
+/// Type-keyed collection of datasets shared across a crawl.
+///
+/// The registry maps a record type `T` to a single [`Data<T>`] handle.
+/// Handlers that extract `Data<T>` without a prior registration get an
+/// [`InMemDataset`] created on first access.
+///
+/// A dataset can additionally be bound to a routing [`Tag`], in which
+/// case requests under that tag see the tag-scoped dataset instead of
+/// the crawl-wide one.
+#[derive(Clone, Default)]
+pub struct DatasetRegistry {
+    inner: Arc<Mutex<HashMap<TypeId, Entry>>>,
+    scoped: Arc<Mutex<HashMap<(Tag, TypeId), Entry>>>,
+    factories: Arc<Mutex<HashMap<TypeId, Factory>>>,
+}
+
+/// Deferred dataset constructor, run on the first access of its type.
+type Factory = Box<dyn Fn() -> Box<dyn Any + Send + Sync> + Send + Sync>;
+
+/// A registered dataset together with the name of its record type.
+struct Entry {
+    name: &'static str,
+    data: Box<dyn Any + Send + Sync>,
+}
+
+impl Entry {
+    fn new<T: Send + Sync + 'static>(data: Data<T>) -> Self {
+        Self {
+            name: any::type_name::<T>(),
+            data: Box::new(data),
+        }
+    }
+}
+
+impl DatasetRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a dataset for the record type `T`, replacing any previous
+    /// registration.
+    pub fn set<T, D>(&self, dataset: D)
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+    {
+        let mut guard = self.inner.lock().expect("registry lock poisoned");
+        guard.insert(TypeId::of::<T>(), Entry::new(Data::new(dataset)));
+    }
+
+    /// Returns the dataset handle registered for `T`, if any.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Data<T>> {
+        let guard = self.inner.lock().expect("registry lock poisoned");
+        guard
+            .get(&TypeId::of::<T>())
+            .and_then(|entry| entry.data.downcast_ref::<Data<T>>())
+            .cloned()
+    }
+
+    /// Registers a factory constructing the dataset for `T` lazily.
+    ///
+    /// The factory replaces the [`InMemDataset`] fallback: it runs once,
+    /// on the first access of `Data<T>`, and its result is registered
+    /// like an eagerly set dataset. An explicit [`DatasetRegistry::set`]
+    /// still wins — the factory only fills the gap.
+    ///
+    /// The factory is called while the registry lock is held, so exactly
+    /// one invocation happens even when handlers race on the first
+    /// access; keep it cheap and non-blocking (construct the handle, do
+    /// not await connections in it).
+    pub fn set_factory<T, D, F>(&self, factory: F)
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+        F: Fn() -> D + Send + Sync + 'static,
+    {
+        let mut guard = self.factories.lock().expect("registry lock poisoned");
+        let factory: Factory = Box::new(move || Box::new(Data::new(factory())));
+        guard.insert(TypeId::of::<T>(), factory);
+    }
+
+    /// Returns the dataset handle for `T`, registering one on first
+    /// access: from the factory of [`DatasetRegistry::set_factory`] when
+    /// one is set, an [`InMemDataset`] otherwise.
+    pub fn get_or_default<T: Send + Sync + 'static>(&self) -> Data<T> {
+        let mut guard = self.inner.lock().expect("registry lock poisoned");
+        let entry = guard.entry(TypeId::of::<T>()).or_insert_with(|| {
+            let factories = self.factories.lock().expect("registry lock poisoned");
+            match factories.get(&TypeId::of::<T>()) {
+                Some(factory) => Entry {
+                    name: any::type_name::<T>(),
+                    data: factory(),
+                },
+                None => Entry::new(Data::new(InMemDataset::<T>::queue())),
+            }
+        });
+
+        entry
+            .data
+            .downcast_ref::<Data<T>>()
+            .cloned()
+            .expect("registry entry has the keyed type")
+    }
+
+    /// Registers a dataset for the record type `T` under the given tag,
+    /// replacing any previous binding for that tag.
+    pub fn set_for_tag<T, D>(&self, tag: impl Into<Tag>, dataset: D)
+    where
+        T: Send + Sync + 'static,
+        D: Dataset<T> + Clone,
+    {
+        let mut guard = self.scoped.lock().expect("registry lock poisoned");
+        guard.insert((tag.into(), TypeId::of::<T>()), Entry::new(Data::new(dataset)));
+    }
+
+    /// Returns the dataset handle for `T` as seen under the given tag:
+    /// the tag-scoped binding when present, otherwise the crawl-wide
+    /// dataset (registered on first access).
+    pub fn get_scoped<T: Send + Sync + 'static>(&self, tag: &Tag) -> Data<T> {
+        let guard = self.scoped.lock().expect("registry lock poisoned");
+        let entry = guard.get(&(tag.clone(), TypeId::of::<T>()));
+        match entry.and_then(|entry| entry.data.downcast_ref::<Data<T>>()) {
+            Some(data) => data.clone(),
+            None => {
+                drop(guard);
+                self.get_or_default()
+            }
+        }
+    }
+
+    /// Returns the record type names currently registered, sorted.
+    ///
+    /// Diagnostic aid: an empty `Data<T>` in a handler is often a dataset
+    /// registered under a different record type, which shows up here.
+    pub fn names(&self) -> Vec<&'static str> {
+        let guard = self.inner.lock().expect("registry lock poisoned");
+        let mut names: Vec<_> = guard.values().map(|entry| entry.name).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl fmt::Debug for DatasetRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatasetRegistry").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn registered_dataset_is_shared() {
+        let registry = DatasetRegistry::new();
+        let dataset = InMemDataset::<u32>::queue();
+        registry.set(dataset.clone());
+
+        registry.get::<u32>().unwrap().write(7).await.unwrap();
+        assert_eq!(dataset.read().await.unwrap(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn tag_scoped_dataset_shadows_the_global_one() {
+        let registry = DatasetRegistry::new();
+        let products = InMemDataset::<u32>::queue();
+        registry.set(InMemDataset::<u32>::queue());
+        registry.set_for_tag("product", products.clone());
+
+        let tag = Tag::from("product");
+        registry.get_scoped::<u32>(&tag).write(7).await.unwrap();
+        assert_eq!(products.read().await.unwrap(), Some(7));
+
+        // Other tags still resolve to the crawl-wide dataset.
+        let other = registry.get_scoped::<u32>(&Tag::Fallback);
+        assert!(other.read().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn names_reflect_registered_record_types() {
+        let registry = DatasetRegistry::new();
+        assert!(registry.names().is_empty());
+
+        registry.set(InMemDataset::<u32>::queue());
+        registry.get_or_default::<String>();
+
+        assert_eq!(registry.names(), vec!["alloc::string::String", "u32"]);
+    }
+
+    #[tokio::test]
+    async fn factory_replaces_the_in_memory_fallback() {
+        let registry = DatasetRegistry::new();
+        let backing = InMemDataset::<u32>::queue();
+        let dataset = backing.clone();
+        registry.set_factory(move || dataset.clone());
+
+        // First access runs the factory; later accesses reuse the result.
+        registry.get_or_default::<u32>().write(7).await.unwrap();
+        assert_eq!(backing.read().await.unwrap(), Some(7));
+
+        // An explicit registration wins over the factory.
+        let registry = DatasetRegistry::new();
+        let eager = InMemDataset::<u32>::queue();
+        registry.set(eager.clone());
+        let dataset = backing.clone();
+        registry.set_factory(move || dataset.clone());
+
+        registry.get_or_default::<u32>().write(9).await.unwrap();
+        assert_eq!(eager.read().await.unwrap(), Some(9));
+    }
+
+    #[tokio::test]
+    async fn default_dataset_created_on_first_access() {
+        let registry = DatasetRegistry::new();
+        assert!(registry.get::<String>().is_none());
+
+        let data = registry.get_or_default::<String>();
+        data.write("record".to_owned()).await.unwrap();
+
+        let same = registry.get_or_default::<String>();
+        assert_eq!(same.read().await.unwrap(), Some("record".to_owned()));
+    }
+}
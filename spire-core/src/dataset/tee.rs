@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+
+use crate::dataset::Dataset;
+use crate::Result;
+
+/// Dataset adapter duplicating every write into a secondary dataset.
+///
+/// Created by [`DatasetExt::tee`]. Writes go to the primary first and then
+/// to the secondary, failing if either write fails; reads, `len` and
+/// `is_empty` are served by the primary alone, so the secondary acts as a
+/// write-only copy (e.g. a persistent dataset mirroring an in-memory one).
+///
+/// [`DatasetExt::tee`]: crate::dataset::DatasetExt::tee
+#[derive(Debug, Clone, Default)]
+pub struct Tee<A, B> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A, B> Tee<A, B> {
+    pub(crate) fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+}
+
+#[async_trait]
+impl<T, A, B> Dataset<T> for Tee<A, B>
+where
+    T: Clone + Send + Sync + 'static,
+    A: Dataset<T>,
+    B: Dataset<T>,
+{
+    async fn write(&self, data: T) -> Result<()> {
+        self.primary.write(data.clone()).await?;
+        self.secondary.write(data).await
+    }
+
+    async fn read(&self) -> Result<Option<T>> {
+        self.primary.read().await
+    }
+
+    async fn len(&self) -> usize {
+        self.primary.len().await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::{DatasetExt, InMemDataset};
+
+    #[tokio::test]
+    async fn writes_reach_both_datasets() {
+        let primary = InMemDataset::<u32>::queue();
+        let secondary = InMemDataset::<u32>::queue();
+        let tee = primary.clone().tee(secondary.clone());
+
+        tee.write(1).await.unwrap();
+        tee.write(2).await.unwrap();
+        assert_eq!(primary.len().await, 2);
+        assert_eq!(secondary.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn reads_drain_only_the_primary() {
+        let primary = InMemDataset::<u32>::queue();
+        let secondary = InMemDataset::<u32>::queue();
+        let tee = primary.tee(secondary.clone());
+
+        tee.write(7).await.unwrap();
+        assert_eq!(tee.read().await.unwrap(), Some(7));
+        assert_eq!(tee.len().await, 0);
+        assert_eq!(secondary.len().await, 1);
+    }
+}
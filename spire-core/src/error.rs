@@ -0,0 +1,147 @@
+//! Error and result types shared by every crate in the framework.
+
+use std::fmt;
+
+use crate::context::TagQuery;
+
+/// A boxed, type-erased error source.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Specialized [`Result`] alias used throughout the framework.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Broad classification of an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Failure while exchanging a request for a response.
+    Http,
+    /// Failure originating in a backend or its resource pool.
+    Backend,
+    /// Failure while building or extracting from a [`Context`].
+    ///
+    /// [`Context`]: crate::context::Context
+    Context,
+    /// An operation did not finish within its time budget.
+    Timeout,
+    /// No client could be acquired from a resource pool.
+    Pool,
+    /// A document or page element lookup failed.
+    Element,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Http => f.write_str("http"),
+            ErrorKind::Backend => f.write_str("backend"),
+            ErrorKind::Context => f.write_str("context"),
+            ErrorKind::Timeout => f.write_str("timeout"),
+            ErrorKind::Pool => f.write_str("pool"),
+            ErrorKind::Element => f.write_str("element"),
+        }
+    }
+}
+
+/// The error type returned by backends, datasets and extractors.
+///
+/// An error optionally carries a [`TagQuery`] that instructs the runner to
+/// drop queued tasks matching the query when the error surfaces.
+pub struct Error {
+    kind: ErrorKind,
+    source: BoxError,
+    query: Option<TagQuery>,
+}
+
+impl Error {
+    /// Creates a new error of the given kind from an underlying source.
+    pub fn new(kind: ErrorKind, source: impl Into<BoxError>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+            query: None,
+        }
+    }
+
+    /// Creates a new error of the given kind from a plain message.
+    pub fn msg(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self::new(kind, message.into())
+    }
+
+    /// Returns the broad classification of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Attaches a [`TagQuery`] targeting queued tasks to drop.
+    pub fn with_query(mut self, query: TagQuery) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// Returns the attached [`TagQuery`], if any.
+    pub fn query(&self) -> Option<&TagQuery> {
+        self.query.as_ref()
+    }
+}
+
+impl From<BoxError> for Error {
+    /// Adopts a type-erased error, as produced by standard Tower
+    /// middleware, classifying it as [`ErrorKind::Context`].
+    fn from(source: BoxError) -> Self {
+        // Recover the original kind when the boxed error is one of ours.
+        match source.downcast::<Error>() {
+            Ok(error) => *error,
+            Err(source) => Self::new(ErrorKind::Context, source),
+        }
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.source)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::Tag;
+
+    #[test]
+    fn display_includes_kind() {
+        let error = Error::msg(ErrorKind::Backend, "pool unavailable");
+        assert_eq!(error.to_string(), "backend: pool unavailable");
+    }
+
+    #[test]
+    fn boxed_errors_keep_their_kind() {
+        let boxed: BoxError = Box::new(Error::msg(ErrorKind::Timeout, "late"));
+        assert_eq!(Error::from(boxed).kind(), ErrorKind::Timeout);
+
+        let foreign: BoxError = "oops".into();
+        assert_eq!(Error::from(foreign).kind(), ErrorKind::Context);
+    }
+
+    #[test]
+    fn query_is_preserved() {
+        let query = TagQuery::exact(Tag::from("product"));
+        let error = Error::msg(ErrorKind::Http, "oops").with_query(query.clone());
+        assert_eq!(error.query(), Some(&query));
+    }
+}
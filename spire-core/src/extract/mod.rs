@@ -0,0 +1,272 @@
+//! Extractors pulling typed values out of a [`Context`].
+//!
+//! Handler arguments implement [`FromContext`]; extraction runs in
+//! argument order before the handler body executes. A failed extraction
+//! produces a [`Rejection`], which resolves the request to
+//! [`Signal::Skip`] without invoking the handler.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use async_trait::async_trait;
+use http::Uri;
+
+use crate::backend::{Client, Fetcher};
+use crate::context::{Context, Depth, PageContent, RequestQueue, Tag};
+use crate::dataset::Data;
+use crate::signal::{CancelToken, IntoSignal, Signal, SignalStats};
+
+mod state;
+
+pub use state::{FromRef, HostState, State};
+
+/// Rejection produced when an extractor cannot run.
+///
+/// Converts into [`Signal::Skip`], dropping the request without failing
+/// the crawl.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    reason: Cow<'static, str>,
+}
+
+impl Rejection {
+    /// Creates a rejection with the given reason.
+    pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns the human-readable reason of this rejection.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "extractor rejected: {}", self.reason)
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+impl IntoSignal for Rejection {
+    fn into_signal(self) -> Signal {
+        tracing::debug!(reason = %self.reason, "extractor rejected request");
+        Signal::Skip
+    }
+}
+
+/// Types that can be created from a [`Context`] and router state.
+#[async_trait]
+pub trait FromContext<C, S = ()>: Sized {
+    /// The signal produced when extraction fails.
+    ///
+    /// The [`Display`] rendering doubles as the reason string passed to a
+    /// rejection hook; see [`Client::with_rejection_hook`].
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [`Client::with_rejection_hook`]: crate::client::Client::with_rejection_hook
+    type Rejection: IntoSignal + fmt::Display + Send;
+
+    /// Performs the extraction.
+    async fn from_context(cx: &mut Context<C>, state: &S) -> Result<Self, Self::Rejection>;
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Tag
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(cx.tag().clone())
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Uri
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(cx.uri().clone())
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Depth
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Depth(cx.depth()))
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for RequestQueue
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(cx.queue())
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Fetcher
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        cx.fetcher()
+            .ok_or_else(|| Rejection::new("Fetcher: not provided by the run loop"))
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for SignalStats
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        cx.signal_stats()
+            .ok_or_else(|| Rejection::new("SignalStats: not provided by the run loop"))
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for CancelToken
+where
+    C: Send,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        cx.cancel_token()
+            .ok_or_else(|| Rejection::new("CancelToken: not provided by the run loop"))
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for PageContent
+where
+    C: Client,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let response = cx
+            .resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("PageContent: {error}")))?;
+        response
+            .extensions()
+            .get::<PageContent>()
+            .cloned()
+            .ok_or_else(|| Rejection::new("PageContent: not provided by the backend"))
+    }
+}
+
+/// Extractor for a request extension of type `T`.
+///
+/// Mirrors axum's `Extension`: the value is looked up by its type in the
+/// extensions of the current request and cloned out, rejecting when no
+/// value of that type was inserted. See [`Context::insert_extension`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Extension<T>(pub T);
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Extension<T>
+where
+    C: Send,
+    S: Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        cx.get_extension::<T>().cloned().map(Extension).ok_or_else(|| {
+            let name = std::any::type_name::<T>();
+            Rejection::new(format!("Extension: no `{name}` on the request"))
+        })
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Data<T>
+where
+    C: Send,
+    S: Sync,
+    T: Send + Sync + 'static,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(cx.dataset::<T>())
+    }
+}
+
+/// Extractor grabbing several typed dataset handles in one argument.
+///
+/// A handler writing three record types would otherwise take three
+/// [`Data<T>`] arguments; this collapses them into one:
+///
+/// ```ignore
+/// async fn handler(Datasets((products, prices, errors)): Datasets<(Data<Product>, Data<Price>, Data<CrawlError>)>) {
+///     /* ... */
+/// }
+/// ```
+///
+/// Each handle comes from the same registry lookup as a standalone
+/// [`Data<T>`] argument, tag-scoped bindings included.
+#[derive(Debug, Clone)]
+pub struct Datasets<T>(pub T);
+
+macro_rules! impl_datasets {
+    ($($ty:ident),+) => {
+        #[async_trait]
+        impl<C, S, $($ty,)+> FromContext<C, S> for Datasets<($(Data<$ty>,)+)>
+        where
+            C: Send,
+            S: Sync,
+            $($ty: Send + Sync + 'static,)+
+        {
+            type Rejection = Rejection;
+
+            async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+                Ok(Datasets(($(cx.dataset::<$ty>(),)+)))
+            }
+        }
+    };
+}
+
+impl_datasets!(T1);
+impl_datasets!(T1, T2);
+impl_datasets!(T1, T2, T3);
+impl_datasets!(T1, T2, T3, T4);
+impl_datasets!(T1, T2, T3, T4, T5);
+impl_datasets!(T1, T2, T3, T4, T5, T6);
+impl_datasets!(T1, T2, T3, T4, T5, T6, T7);
+impl_datasets!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::context::Context;
+use crate::extract::{FromContext, Rejection};
+
+/// Extractor for (a part of) the state a [`Router`] was built with.
+///
+/// [`Router`]: crate::routing::Router
+#[derive(Debug, Clone, Copy, Default)]
+pub struct State<S>(pub S);
+
+/// Conversion from a reference to the router state.
+///
+/// Implemented for every `Clone` state by cloning it wholesale; implement
+/// it manually (or for fields of a composite state) to extract substates
+/// through [`State`].
+pub trait FromRef<T> {
+    /// Converts from a reference to the input state.
+    fn from_ref(input: &T) -> Self;
+}
+
+impl<T: Clone> FromRef<T> for T {
+    fn from_ref(input: &T) -> Self {
+        input.clone()
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for State<T>
+where
+    C: Send,
+    S: Sync,
+    T: FromRef<S>,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(_cx: &mut Context<C>, state: &S) -> Result<Self, Self::Rejection> {
+        Ok(State(T::from_ref(state)))
+    }
+}
+
+/// Extractor for per-host values carried in the router state.
+///
+/// Expects the state to provide a `HashMap<String, T>` through [`FromRef`],
+/// keyed by host name; the entry for the current request's host is cloned
+/// out. Extraction rejects (skipping the request) when the request has no
+/// host or the map has no entry for it, so handlers can rely on per-host
+/// configuration being present.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostState<T>(pub T);
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for HostState<T>
+where
+    C: Send,
+    S: Sync,
+    HashMap<String, T>: FromRef<S>,
+    T: Clone + Send,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(host) = cx.uri().host() else {
+            return Err(Rejection::new("HostState: request has no host"));
+        };
+
+        let map = HashMap::<String, T>::from_ref(state);
+        match map.get(host) {
+            Some(value) => Ok(HostState(value.clone())),
+            None => Err(Rejection::new(format!(
+                "HostState: no state for host `{host}`"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::context_for;
+
+    #[tokio::test]
+    async fn extracts_entry_for_current_host() {
+        let state: HashMap<String, u32> = [("example.com".to_owned(), 7)].into();
+        let (mut cx, _queue) = context_for("https://example.com/page", Noop::new());
+
+        let HostState(value) = HostState::<u32>::from_context(&mut cx, &state).await.unwrap();
+        assert_eq!(value, 7);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_hosts() {
+        let state: HashMap<String, u32> = HashMap::new();
+        let (mut cx, _queue) = context_for("https://example.com/page", Noop::new());
+
+        let rejection = HostState::<u32>::from_context(&mut cx, &state).await.unwrap_err();
+        assert!(rejection.reason().contains("example.com"));
+    }
+}
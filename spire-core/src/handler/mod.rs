@@ -0,0 +1,155 @@
+//! Handler trait connecting async functions to the router.
+//!
+//! A handler is an async function whose arguments implement
+//! [`FromContext`] and whose return value implements [`IntoSignal`]. The
+//! final argument may instead consume the whole [`Context`] for manual
+//! control over resolution and extraction.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::context::Context;
+use crate::extract::FromContext;
+use crate::signal::{IntoSignal, Signal};
+
+mod service;
+
+pub use service::HandlerService;
+
+/// The boxed future produced by invoking a [`Handler`].
+pub type HandlerFuture = Pin<Box<dyn Future<Output = Signal> + Send>>;
+
+/// An async function usable as a route of a [`Router`].
+///
+/// The `T` parameter is a marker describing the argument list and is
+/// inferred at the [`Router::route`] call site.
+///
+/// [`Router`]: crate::routing::Router
+/// [`Router::route`]: crate::routing::Router::route
+pub trait Handler<T, C, S = ()>: Clone + Send + Sync + Sized + 'static {
+    /// Invokes the handler, extracting its arguments from the context.
+    fn call(self, cx: Context<C>, state: S) -> HandlerFuture;
+
+    /// Wraps this handler into a standalone Tower [`Service`].
+    ///
+    /// The resulting [`HandlerService`] composes with arbitrary Tower
+    /// middleware outside the router and registers via
+    /// [`Router::route_service`].
+    ///
+    /// [`Service`]: tower::Service
+    /// [`Router::route_service`]: crate::routing::Router::route_service
+    fn into_service(self, state: S) -> HandlerService<Self, T, C, S> {
+        HandlerService::new(self, state)
+    }
+}
+
+mod private {
+    /// Marker for handlers built purely from extractors.
+    #[derive(Debug, Clone, Copy)]
+    pub enum ViaExtract {}
+
+    /// Marker for handlers consuming the [`Context`] as the last argument.
+    ///
+    /// [`Context`]: crate::context::Context
+    #[derive(Debug, Clone, Copy)]
+    pub enum ViaContext {}
+}
+
+impl<F, Fut, Res, C, S> Handler<(private::ViaExtract,), C, S> for F
+where
+    F: FnOnce() -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Res> + Send + 'static,
+    Res: IntoSignal,
+    C: Send + 'static,
+    S: Send + Sync + 'static,
+{
+    fn call(self, _cx: Context<C>, _state: S) -> HandlerFuture {
+        Box::pin(async move { self().await.into_signal() })
+    }
+}
+
+impl<F, Fut, Res, C, S> Handler<(private::ViaContext,), C, S> for F
+where
+    F: FnOnce(Context<C>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Res> + Send + 'static,
+    Res: IntoSignal,
+    C: Send + 'static,
+    S: Send + Sync + 'static,
+{
+    fn call(self, cx: Context<C>, _state: S) -> HandlerFuture {
+        Box::pin(async move { self(cx).await.into_signal() })
+    }
+}
+
+macro_rules! impl_handler {
+    ($($ty:ident),+) => {
+        #[allow(non_snake_case)]
+        impl<F, Fut, Res, C, S, $($ty,)+> Handler<(private::ViaExtract, $($ty,)+), C, S> for F
+        where
+            F: FnOnce($($ty,)+) -> Fut + Clone + Send + Sync + 'static,
+            Fut: Future<Output = Res> + Send + 'static,
+            Res: IntoSignal,
+            C: Send + 'static,
+            S: Send + Sync + 'static,
+            $($ty: FromContext<C, S> + Send + 'static,)+
+        {
+            fn call(self, mut cx: Context<C>, state: S) -> HandlerFuture {
+                Box::pin(async move {
+                    $(
+                        let $ty = match $ty::from_context(&mut cx, &state).await {
+                            Ok(value) => value,
+                            Err(rejection) => {
+                                let reason = rejection.to_string();
+                                let signal = rejection.into_signal();
+                                if matches!(signal, Signal::Skip) {
+                                    cx.notify_rejection(&reason);
+                                }
+                                return signal;
+                            }
+                        };
+                    )+
+                    self($($ty,)+).await.into_signal()
+                })
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F, Fut, Res, C, S, $($ty,)+> Handler<(private::ViaContext, $($ty,)+), C, S> for F
+        where
+            F: FnOnce($($ty,)+ Context<C>) -> Fut + Clone + Send + Sync + 'static,
+            Fut: Future<Output = Res> + Send + 'static,
+            Res: IntoSignal,
+            C: Send + 'static,
+            S: Send + Sync + 'static,
+            $($ty: FromContext<C, S> + Send + 'static,)+
+        {
+            fn call(self, mut cx: Context<C>, state: S) -> HandlerFuture {
+                Box::pin(async move {
+                    $(
+                        let $ty = match $ty::from_context(&mut cx, &state).await {
+                            Ok(value) => value,
+                            Err(rejection) => {
+                                let reason = rejection.to_string();
+                                let signal = rejection.into_signal();
+                                if matches!(signal, Signal::Skip) {
+                                    cx.notify_rejection(&reason);
+                                }
+                                return signal;
+                            }
+                        };
+                    )+
+                    self($($ty,)+ cx).await.into_signal()
+                })
+            }
+        }
+    };
+}
+
+impl_handler!(T1);
+impl_handler!(T1, T2);
+impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -0,0 +1,106 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use tower::Service;
+
+use crate::context::Context;
+use crate::handler::Handler;
+use crate::signal::Signal;
+
+/// [`Service`] adapter executing a [`Handler`] for each incoming [`Context`].
+///
+/// Produced by the router when a handler is registered on a route.
+pub struct HandlerService<H, T, C, S> {
+    handler: H,
+    state: S,
+    _marker: PhantomData<fn() -> (T, C)>,
+}
+
+impl<H, T, C, S> HandlerService<H, T, C, S> {
+    pub(crate) fn new(handler: H, state: S) -> Self {
+        Self {
+            handler,
+            state,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<H, T, C, S> Service<Context<C>> for HandlerService<H, T, C, S>
+where
+    H: Handler<T, C, S>,
+    C: Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    type Response = Signal;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, cx: Context<C>) -> Self::Future {
+        let handler = self.handler.clone();
+        let state = self.state.clone();
+        Box::pin(async move { Ok(handler.call(cx, state).await) })
+    }
+}
+
+impl<H: Clone, T, C, S: Clone> Clone for HandlerService<H, T, C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            state: self.state.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<H, T, C, S> fmt::Debug for HandlerService<H, T, C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandlerService").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tower::timeout::TimeoutLayer;
+    use tower::{ServiceBuilder, ServiceExt};
+
+    use crate::backend::utils::Noop;
+    use crate::handler::Handler;
+    use crate::signal::Signal;
+    use crate::test_utils::context_for;
+
+    async fn handler() {}
+
+    #[tokio::test]
+    async fn composes_with_timeout_layer() {
+        let service = ServiceBuilder::new()
+            .layer(TimeoutLayer::new(Duration::from_secs(1)))
+            .service(handler.into_service(()));
+
+        let (cx, _queue) = context_for("https://example.com/", Noop::new());
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+
+    #[tokio::test]
+    async fn registers_via_route_service() {
+        let router = crate::Router::<Noop>::new()
+            .route_service("page", handler.into_service(()));
+        let routes = router.into_routes();
+
+        let route = routes.find(&"page".into()).unwrap();
+        let (cx, _queue) = context_for("https://example.com/", Noop::new());
+        let signal = route.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+}
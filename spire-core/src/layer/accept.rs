@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use http::header::{HeaderValue, ACCEPT};
+use tower::{Layer, Service};
+
+use crate::backend::Client;
+use crate::context::{Context, Tag};
+use crate::signal::Signal;
+
+/// Layer negotiating the `Accept` header per routing tag.
+///
+/// API routes usually want `application/json` while page routes want
+/// `text/html`; registering the mapping once here keeps that header
+/// wrangling out of seed code. An `Accept` header already present on a
+/// request always wins — the layer only fills the header in when the
+/// request carries none, so per-request overrides need no escape hatch.
+#[derive(Debug, Clone, Default)]
+pub struct AcceptLayer {
+    accepts: HashMap<Tag, HeaderValue>,
+}
+
+impl AcceptLayer {
+    /// Creates a layer without any negotiated tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends the given `Accept` value on requests routed under the tag.
+    pub fn accept(mut self, tag: impl Into<Tag>, value: HeaderValue) -> Self {
+        self.accepts.insert(tag.into(), value);
+        self
+    }
+
+    /// Sends `Accept: application/json` on requests routed under the tag.
+    pub fn json(self, tag: impl Into<Tag>) -> Self {
+        self.accept(tag, HeaderValue::from_static("application/json"))
+    }
+
+    /// Sends `Accept: text/html` on requests routed under the tag.
+    pub fn html(self, tag: impl Into<Tag>) -> Self {
+        self.accept(tag, HeaderValue::from_static("text/html"))
+    }
+}
+
+impl<S> Layer<S> for AcceptLayer {
+    type Service = Accept<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Accept {
+            inner,
+            accepts: self.accepts.clone(),
+        }
+    }
+}
+
+/// Middleware service produced by [`AcceptLayer`].
+#[derive(Debug, Clone)]
+pub struct Accept<S> {
+    inner: S,
+    accepts: HashMap<Tag, HeaderValue>,
+}
+
+impl<C, S> Service<Context<C>> for Accept<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let negotiated = self.accepts.get(cx.tag()).cloned();
+
+        Box::pin(async move {
+            if let (Some(value), Some(request)) = (negotiated, cx.get_mut()) {
+                let headers = request.headers_mut();
+                if !headers.contains_key(ACCEPT) {
+                    headers.insert(ACCEPT, value);
+                }
+            }
+
+            inner.call(cx).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::tagged_context_for;
+
+    /// Records the `Accept` header seen by the inner service.
+    macro_rules! observing_service {
+        ($seen:expr) => {{
+            let seen = $seen.clone();
+            tower::service_fn(move |cx: Context<Noop>| {
+                let seen = seen.clone();
+                async move {
+                    let accept = cx
+                        .request()
+                        .and_then(|request| request.headers().get(ACCEPT))
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_owned);
+                    seen.lock().unwrap().push(accept);
+                    Ok::<_, Infallible>(Signal::Continue)
+                }
+            })
+        }};
+    }
+
+    #[tokio::test]
+    async fn negotiates_per_tag() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let service = AcceptLayer::new()
+            .json("api")
+            .html("page")
+            .layer(observing_service!(seen));
+
+        let (cx, _queue) = tagged_context_for("https://example.com/api", "api", Noop::new());
+        service.clone().oneshot(cx).await.unwrap();
+
+        let (cx, _queue) = tagged_context_for("https://example.com/", "other", Noop::new());
+        service.oneshot(cx).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].as_deref(), Some("application/json"));
+        assert_eq!(seen[1], None);
+    }
+
+    #[tokio::test]
+    async fn explicit_headers_win() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let service = AcceptLayer::new()
+            .json("api")
+            .layer(observing_service!(seen));
+
+        let (mut cx, _queue) = tagged_context_for("https://example.com/api", "api", Noop::new());
+        let request = cx.get_mut().unwrap();
+        request
+            .headers_mut()
+            .insert(ACCEPT, HeaderValue::from_static("text/csv"));
+        service.oneshot(cx).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].as_deref(), Some("text/csv"));
+    }
+}
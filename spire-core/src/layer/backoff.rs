@@ -0,0 +1,306 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::{Duration, SystemTime};
+
+use http::{Extensions, HeaderMap, Method, StatusCode, Uri};
+use tower::{Layer, Service};
+
+use crate::backend::Client;
+use crate::context::{Body, Context, Task};
+use crate::signal::Signal;
+
+/// Layer honoring `Retry-After` on rate-limited responses.
+///
+/// When a response comes back `429 Too Many Requests` or
+/// `503 Service Unavailable` with a `Retry-After` header, the request is
+/// re-enqueued and the crawl backs off for the advertised delay via
+/// [`Signal::Wait`] — the handler never runs on the rate-limited
+/// response. Both the delta-seconds and the HTTP-date forms of the
+/// header are understood; responses without a parseable `Retry-After`
+/// pass through to the route untouched.
+#[derive(Debug, Clone, Default)]
+pub struct BackoffLayer {
+    max_delay: Option<Duration>,
+    jitter: f64,
+}
+
+impl BackoffLayer {
+    /// Creates a layer honoring `Retry-After` delays as-is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the honored delay, guarding against servers advertising
+    /// far-future dates.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Randomizes each delay by up to the given fraction in either
+    /// direction, so `0.2` turns a 10-second delay into 8–12 seconds.
+    ///
+    /// Workers backing off for the exact advertised delay wake up in
+    /// lockstep and hit the host as one burst; a little jitter spreads
+    /// the retries out. The fraction is clamped to `0.0..=1.0`.
+    pub fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl<S> Layer<S> for BackoffLayer {
+    type Service = Backoff<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Backoff {
+            inner,
+            max_delay: self.max_delay,
+            jitter: self.jitter,
+        }
+    }
+}
+
+/// Middleware service produced by [`BackoffLayer`].
+#[derive(Debug, Clone)]
+pub struct Backoff<S> {
+    inner: S,
+    max_delay: Option<Duration>,
+    jitter: f64,
+}
+
+/// Request parts retained for re-enqueueing before resolution consumes
+/// the original.
+struct RetryRequest {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    extensions: Extensions,
+    body: Body,
+}
+
+impl RetryRequest {
+    fn into_task(self) -> Option<Task> {
+        let mut builder = http::Request::builder().method(self.method).uri(self.uri);
+        *builder.headers_mut()? = self.headers;
+        // Extensions carry request markers (priorities, session
+        // affinities); losing them would change how the retry behaves.
+        *builder.extensions_mut()? = self.extensions;
+        builder.body(self.body).ok().map(Task::new)
+    }
+}
+
+/// Scales `delay` by a random factor in `1.0 - jitter ..= 1.0 + jitter`.
+fn apply_jitter(delay: Duration, jitter: f64) -> Duration {
+    if jitter <= 0.0 {
+        return delay;
+    }
+
+    let factor = 1.0 + jitter * (2.0 * random_unit() - 1.0);
+    delay.mul_f64(factor)
+}
+
+/// Returns a uniform value in `0.0..1.0` without a `rand` dependency,
+/// from the per-process randomness seeding [`RandomState`].
+///
+/// [`RandomState`]: std::collections::hash_map::RandomState
+fn random_unit() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Parses both forms of `Retry-After`: delta-seconds and HTTP-date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+impl<C, S> Service<Context<C>> for Backoff<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let max_delay = self.max_delay;
+        let jitter = self.jitter;
+
+        Box::pin(async move {
+            let retry = cx.get_mut().map(|request| RetryRequest {
+                method: request.method().clone(),
+                uri: request.uri().clone(),
+                headers: request.headers().clone(),
+                extensions: request.extensions().clone(),
+                body: request.body().clone(),
+            });
+
+            let response = match cx.resolve().await {
+                Ok(response) => response,
+                Err(error) => return Ok(Signal::Fail(error)),
+            };
+
+            let limited = matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE,
+            );
+            let delay = limited
+                .then(|| response.headers().get(http::header::RETRY_AFTER))
+                .flatten()
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+
+            let Some(delay) = delay else {
+                return inner.call(cx).await;
+            };
+            let delay = match max_delay {
+                Some(max_delay) => delay.min(max_delay),
+                None => delay,
+            };
+            let delay = apply_jitter(delay, jitter);
+
+            let task = retry.and_then(RetryRequest::into_task);
+            if let Some(task) = task {
+                let task = task.with_tag(cx.tag().clone());
+                tracing::debug!(uri = %cx.uri(), ?delay, "rate limited; re-enqueueing");
+                if let Err(error) = cx.queue().append_request(task).await {
+                    return Ok(Signal::Fail(error));
+                }
+            }
+
+            Ok(Signal::Wait(delay))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::context::{Request, Response};
+    use crate::test_utils::context_for;
+    use crate::Result;
+
+    /// Client answering `429` with `Retry-After` once, then `200`.
+    #[derive(Debug, Clone, Default)]
+    struct RateLimited {
+        tripped: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl Client for RateLimited {
+        async fn resolve(&mut self, _request: Request) -> Result<Response> {
+            let builder = match self.tripped.swap(true, Ordering::Relaxed) {
+                false => http::Response::builder()
+                    .status(StatusCode::TOO_MANY_REQUESTS)
+                    .header(http::header::RETRY_AFTER, "2"),
+                true => http::Response::builder().status(StatusCode::OK),
+            };
+
+            Ok(builder.body(Body::empty()).expect("response should build"))
+        }
+    }
+
+    #[test]
+    fn parses_both_header_forms() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        // A date in the past clamps to zero rather than failing.
+        let past = parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(past, Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("not a date"), None);
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let base = Duration::from_secs(10);
+        assert_eq!(apply_jitter(base, 0.0), base);
+
+        for _ in 0..64 {
+            let jittered = apply_jitter(base, 0.2);
+            assert!(jittered >= Duration::from_secs(8), "{jittered:?}");
+            assert!(jittered <= Duration::from_secs(12), "{jittered:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn jittered_wait_spreads_around_the_base() {
+        let service = BackoffLayer::new()
+            .with_jitter(0.5)
+            .layer(tower::service_fn(|_cx| async {
+                Ok::<_, std::convert::Infallible>(Signal::Continue)
+            }));
+
+        let (cx, _queue) = context_for("https://example.com/", RateLimited::default());
+        let signal = service.oneshot(cx).await.unwrap();
+        let Signal::Wait(delay) = signal else {
+            panic!("expected a wait, got {signal:?}");
+        };
+        assert!(delay >= Duration::from_secs(1), "{delay:?}");
+        assert!(delay <= Duration::from_secs(3), "{delay:?}");
+    }
+
+    #[tokio::test]
+    async fn rate_limited_response_reenqueues_and_waits() {
+        let service = BackoffLayer::new().layer(tower::service_fn(|_cx| async {
+            Ok::<_, std::convert::Infallible>(Signal::Continue)
+        }));
+
+        let client = RateLimited::default();
+        let (cx, queue) = context_for("https://example.com/", client.clone());
+        let signal = service.clone().oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Wait(delay) if delay == Duration::from_secs(2)));
+
+        // The request went back onto the queue and passes once the
+        // server stops rate limiting.
+        let task = queue.read().await.unwrap().unwrap();
+        assert_eq!(task.uri().to_string(), "https://example.com/");
+
+        let (cx, _queue) = context_for("https://example.com/", client);
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+
+    #[tokio::test]
+    async fn retry_keeps_request_extensions() {
+        use crate::context::Priority;
+
+        let service = BackoffLayer::new().layer(tower::service_fn(|_cx| async {
+            Ok::<_, std::convert::Infallible>(Signal::Continue)
+        }));
+
+        let (mut cx, queue) = context_for("https://example.com/", RateLimited::default());
+        cx.get_mut().unwrap().extensions_mut().insert(Priority(200));
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Wait(_)));
+
+        // Markers like priorities and session affinities must survive the
+        // re-enqueue, or the retry behaves differently from the original.
+        let task = queue.read().await.unwrap().unwrap();
+        assert_eq!(task.priority(), 200);
+    }
+}
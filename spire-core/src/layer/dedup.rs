@@ -0,0 +1,157 @@
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hasher;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+
+use http::Uri;
+use tower::{Layer, Service};
+
+use crate::backend::Client;
+use crate::context::Context;
+use crate::dataset::{Data, Dataset};
+use crate::signal::Signal;
+
+/// Layer skipping responses whose body was already seen under another URL.
+///
+/// The response body is resolved and hashed before the route runs; if the
+/// hash matches an earlier response, the request short-circuits to
+/// [`Signal::Skip`]. This catches content duplicates (mirrors, print/AMP
+/// variants) that URL-level deduplication cannot.
+///
+/// Newly seen `(hash, canonical URL)` pairs can be persisted to a
+/// pluggable dataset via [`DedupLayer::with_store`].
+#[derive(Debug, Clone, Default)]
+pub struct DedupLayer {
+    seen: Arc<Mutex<HashMap<u64, Uri>>>,
+    store: Option<Data<(u64, Uri)>>,
+}
+
+impl DedupLayer {
+    /// Creates a layer with an empty in-memory seen-hashes index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes every newly seen `(hash, canonical URL)` pair to the given
+    /// dataset, exposing the seen set for persistence or inspection.
+    pub fn with_store<D>(mut self, dataset: D) -> Self
+    where
+        D: Dataset<(u64, Uri)> + Clone,
+    {
+        self.store = Some(Data::new(dataset));
+        self
+    }
+}
+
+impl<S> Layer<S> for DedupLayer {
+    type Service = Dedup<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Dedup {
+            inner,
+            seen: self.seen.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+/// Middleware service produced by [`DedupLayer`].
+#[derive(Debug, Clone)]
+pub struct Dedup<S> {
+    inner: S,
+    seen: Arc<Mutex<HashMap<u64, Uri>>>,
+    store: Option<Data<(u64, Uri)>>,
+}
+
+impl<C, S> Service<Context<C>> for Dedup<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let seen = self.seen.clone();
+        let store = self.store.clone();
+
+        Box::pin(async move {
+            let response = match cx.resolve().await {
+                Ok(response) => response,
+                Err(error) => return Ok(Signal::Fail(error)),
+            };
+
+            let mut hasher = DefaultHasher::new();
+            hasher.write(response.body().bytes());
+            let hash = hasher.finish();
+
+            let uri = cx.uri().clone();
+            let first_seen = match seen.lock().unwrap().entry(hash) {
+                Entry::Occupied(entry) => {
+                    let canonical = entry.get().clone();
+                    tracing::debug!(%uri, %canonical, "skipping content duplicate");
+                    return Ok(Signal::Skip);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(uri.clone());
+                    uri
+                }
+            };
+
+            if let Some(store) = &store {
+                if let Err(error) = store.write((hash, first_seen)).await {
+                    tracing::debug!(%error, "failed to record seen hash");
+                }
+            }
+
+            inner.call(cx).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::dataset::InMemDataset;
+    use crate::test_utils::{context_for, StaticClient};
+
+    #[tokio::test]
+    async fn skips_repeated_content() {
+        let client = StaticClient::new("/article", "shared content");
+        let store = Data::new(InMemDataset::queue());
+        let service = DedupLayer::new().with_store(store.clone()).layer(
+            tower::service_fn(|_cx| async { Ok::<_, std::convert::Infallible>(Signal::Continue) }),
+        );
+
+        // Every path other than `/article` resolves to an empty body, so
+        // the second empty response is a content duplicate of the first.
+        let (cx, _queue) = context_for("https://example.com/article", client.clone());
+        assert!(matches!(service.clone().oneshot(cx).await.unwrap(), Signal::Continue));
+
+        let (cx, _queue) = context_for("https://example.com/a", client.clone());
+        assert!(matches!(service.clone().oneshot(cx).await.unwrap(), Signal::Continue));
+
+        let (cx, _queue) = context_for("https://mirror.example.com/b", client);
+        assert!(matches!(service.oneshot(cx).await.unwrap(), Signal::Skip));
+
+        let seen = store.read_all().await.unwrap();
+        let canonicals: Vec<_> = seen.iter().map(|(_, uri)| uri.to_string()).collect();
+        assert_eq!(
+            canonicals,
+            vec!["https://example.com/article", "https://example.com/a"]
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use tower::{Layer, Service};
+
+use super::include::{seed_from_sitemap_url, SITEMAP_LIMITS};
+use crate::backend::Client;
+use crate::context::{Context, Tag};
+use crate::layer::{fetch_text, FetchLimits};
+use crate::signal::Signal;
+
+/// Default bounds on a `robots.txt` fetch: 10 seconds and 10 MiB.
+const ROBOTS_LIMITS: FetchLimits = FetchLimits {
+    timeout: Duration::from_secs(10),
+    max_size: 10 * 1024 * 1024,
+};
+
+/// Layer applying `robots.txt` exclusion rules before dispatch.
+///
+/// The first request for a host fetches and caches `robots.txt` through
+/// the backend client of that request; requests whose path the rules
+/// disallow resolve to [`Signal::Skip`] without reaching the inner
+/// service. Fetch failures — including timed-out or oversized files,
+/// see [`ExcludeLayer::with_fetch_timeout`] — fail open: the host is
+/// treated as allowing everything.
+#[derive(Debug, Clone)]
+pub struct ExcludeLayer {
+    user_agent: String,
+    limits: FetchLimits,
+    discover_sitemaps: bool,
+    cache: Arc<Mutex<HashMap<String, RobotsRules>>>,
+}
+
+impl ExcludeLayer {
+    /// Creates a layer evaluating rules for the `*` user-agent group.
+    pub fn new() -> Self {
+        Self::with_user_agent("*")
+    }
+
+    /// Creates a layer evaluating rules for the given user-agent.
+    pub fn with_user_agent(user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            limits: ROBOTS_LIMITS,
+            discover_sitemaps: false,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Seeds the request queue from the `Sitemap:` directives of each
+    /// fetched `robots.txt`.
+    ///
+    /// When the rules for a host are first fetched, every sitemap the
+    /// file points to is handed to the same seeding pipeline as
+    /// [`IncludeLayer`], enqueueing its entries under [`Tag::Fallback`].
+    /// Combined with the exclusion rules themselves this gives a
+    /// zero-config "crawl everything robots points to" mode without
+    /// stacking a separate [`IncludeLayer`] — which remains the right
+    /// tool when the sitemap location is known up front or the entries
+    /// need a dedicated tag or `lastmod` cutoff.
+    ///
+    /// [`IncludeLayer`]: crate::layer::IncludeLayer
+    pub fn with_sitemap_discovery(mut self, discover: bool) -> Self {
+        self.discover_sitemaps = discover;
+        self
+    }
+
+    /// Caps the time spent fetching a `robots.txt` file.
+    ///
+    /// A fetch exceeding the cap fails open; defaults to 10 seconds.
+    pub fn with_fetch_timeout(mut self, timeout: Duration) -> Self {
+        self.limits.timeout = timeout;
+        self
+    }
+
+    /// Caps the size of an accepted `robots.txt` file in bytes.
+    ///
+    /// A larger file fails open; defaults to 10 MiB.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.limits.max_size = max_size;
+        self
+    }
+}
+
+impl Default for ExcludeLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for ExcludeLayer {
+    type Service = Exclude<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Exclude {
+            inner,
+            user_agent: self.user_agent.clone(),
+            limits: self.limits,
+            discover_sitemaps: self.discover_sitemaps,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// Middleware service produced by [`ExcludeLayer`].
+#[derive(Debug, Clone)]
+pub struct Exclude<S> {
+    inner: S,
+    user_agent: String,
+    limits: FetchLimits,
+    discover_sitemaps: bool,
+    cache: Arc<Mutex<HashMap<String, RobotsRules>>>,
+}
+
+impl<C, S> Service<Context<C>> for Exclude<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let user_agent = self.user_agent.clone();
+        let limits = self.limits;
+        let discover_sitemaps = self.discover_sitemaps;
+        let cache = self.cache.clone();
+
+        Box::pin(async move {
+            let uri = cx.uri().clone();
+            let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) else {
+                return inner.call(cx).await;
+            };
+
+            let origin = format!("{scheme}://{authority}");
+            let cached = cache.lock().unwrap().get(&origin).cloned();
+            let rules = match cached {
+                Some(rules) => rules,
+                None => {
+                    let rules = fetch_rules(&mut cx, &origin, &user_agent, limits).await;
+                    cache.lock().unwrap().insert(origin, rules.clone());
+                    if discover_sitemaps {
+                        for sitemap in &rules.sitemaps {
+                            seed_from_sitemap_url(&mut cx, sitemap, None, &Tag::Fallback, SITEMAP_LIMITS)
+                                .await;
+                        }
+                    }
+                    rules
+                }
+            };
+
+            if !rules.is_allowed(uri.path()) {
+                tracing::debug!(uri = %uri, "request disallowed by robots.txt");
+                return Ok(Signal::Skip);
+            }
+
+            inner.call(cx).await
+        })
+    }
+}
+
+/// Fetches and parses `robots.txt`, failing open on any error.
+async fn fetch_rules<C: Client>(
+    cx: &mut Context<C>,
+    origin: &str,
+    user_agent: &str,
+    limits: FetchLimits,
+) -> RobotsRules {
+    match fetch_text(cx, origin, "/robots.txt", limits).await {
+        Some(text) => RobotsRules::parse(&text, user_agent),
+        None => RobotsRules::default(),
+    }
+}
+
+/// The subset of `robots.txt` rules applying to one user-agent.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RobotsRules {
+    allow: Vec<String>,
+    disallow: Vec<String>,
+    /// `Sitemap:` directives seen while parsing; seeded from when
+    /// [`ExcludeLayer::with_sitemap_discovery`] is enabled.
+    sitemaps: Vec<String>,
+}
+
+impl RobotsRules {
+    /// Parses the rule groups applying to `user_agent` (or `*`).
+    pub(crate) fn parse(text: &str, user_agent: &str) -> Self {
+        let mut rules = Self::default();
+        let mut group_applies = false;
+        let mut in_group_header = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let field = field.trim().to_ascii_lowercase();
+            let value = value.trim();
+
+            match field.as_str() {
+                "user-agent" => {
+                    // Consecutive `User-agent` lines form one group header.
+                    if !in_group_header {
+                        group_applies = false;
+                        in_group_header = true;
+                    }
+                    if value == "*" || user_agent.to_ascii_lowercase().contains(&value.to_ascii_lowercase()) {
+                        group_applies = true;
+                    }
+                }
+                "allow" if group_applies && !value.is_empty() => {
+                    in_group_header = false;
+                    rules.allow.push(value.to_owned());
+                }
+                "disallow" if group_applies && !value.is_empty() => {
+                    in_group_header = false;
+                    rules.disallow.push(value.to_owned());
+                }
+                "sitemap" => {
+                    rules.sitemaps.push(value.to_owned());
+                }
+                _ => in_group_header = false,
+            }
+        }
+
+        rules
+    }
+
+    /// Returns `true` if the given path may be fetched.
+    ///
+    /// Follows the longest-prefix-match convention: the most specific of
+    /// the matching `Allow`/`Disallow` prefixes wins; ties allow.
+    pub(crate) fn is_allowed(&self, path: &str) -> bool {
+        let longest = |prefixes: &[String]| {
+            prefixes
+                .iter()
+                .filter(|prefix| path.starts_with(prefix.as_str()))
+                .map(|prefix| prefix.len())
+                .max()
+        };
+
+        match (longest(&self.allow), longest(&self.disallow)) {
+            (Some(allow), Some(disallow)) => allow >= disallow,
+            (None, Some(_)) => false,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::{context_for, StaticClient};
+
+    const ROBOTS: &str = "\
+        User-agent: *\n\
+        Disallow: /private/\n\
+        Allow: /private/press/\n\
+        \n\
+        User-agent: spire\n\
+        Disallow: /internal/\n\
+        \n\
+        Sitemap: https://example.com/sitemap.xml\n";
+
+    #[test]
+    fn wildcard_group_rules() {
+        let rules = RobotsRules::parse(ROBOTS, "*");
+        assert!(rules.is_allowed("/public/page"));
+        assert!(!rules.is_allowed("/private/page"));
+        assert!(rules.is_allowed("/private/press/release"));
+    }
+
+    #[test]
+    fn named_group_extends_wildcard() {
+        let rules = RobotsRules::parse(ROBOTS, "spire/0.2");
+        assert!(!rules.is_allowed("/internal/tools"));
+    }
+
+    #[test]
+    fn sitemaps_are_collected() {
+        let rules = RobotsRules::parse(ROBOTS, "*");
+        assert_eq!(rules.sitemaps, vec!["https://example.com/sitemap.xml"]);
+    }
+
+    #[test]
+    fn empty_rules_allow_everything() {
+        let rules = RobotsRules::default();
+        assert!(rules.is_allowed("/anything"));
+    }
+
+    #[tokio::test]
+    async fn skips_disallowed_requests() {
+        let client = StaticClient::new("/robots.txt", ROBOTS);
+        let service = ExcludeLayer::new().layer(tower::service_fn(|_cx| async {
+            Ok::<_, std::convert::Infallible>(Signal::Continue)
+        }));
+
+        let (cx, _queue) = context_for("https://example.com/private/page", client.clone());
+        let signal = service.clone().oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Skip));
+
+        let (cx, _queue) = context_for("https://example.com/public/page", client);
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+
+    #[tokio::test]
+    async fn discovered_sitemaps_seed_the_queue() {
+        use crate::context::{Body, Request, Response};
+
+        const SITEMAP: &str = "\
+            <urlset>\n\
+              <url><loc>https://example.com/a</loc></url>\n\
+              <url><loc>https://example.com/b</loc></url>\n\
+            </urlset>";
+
+        /// Client serving both the rules file and the sitemap it points to.
+        #[derive(Debug, Clone)]
+        struct RobotsAndSitemap;
+
+        #[async_trait::async_trait]
+        impl Client for RobotsAndSitemap {
+            async fn resolve(&mut self, request: Request) -> crate::Result<Response> {
+                let body = match request.uri().path() {
+                    "/robots.txt" => Body::new(ROBOTS),
+                    "/sitemap.xml" => Body::new(SITEMAP),
+                    _ => Body::empty(),
+                };
+                Ok(http::Response::builder()
+                    .status(http::StatusCode::OK)
+                    .body(body)
+                    .expect("static response should always build"))
+            }
+        }
+
+        let inner = || {
+            tower::service_fn(|_cx| async { Ok::<_, std::convert::Infallible>(Signal::Continue) })
+        };
+
+        // Discovery is opt-in: the plain layer leaves the queue alone.
+        let service = ExcludeLayer::new().layer(inner());
+        let (cx, queue) = context_for("https://example.com/", RobotsAndSitemap);
+        service.oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 0);
+
+        let service = ExcludeLayer::new()
+            .with_sitemap_discovery(true)
+            .layer(inner());
+        let (cx, queue) = context_for("https://example.com/", RobotsAndSitemap);
+        service.clone().oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 2);
+
+        // The host's rules are cached now; nothing is seeded again.
+        let (cx, queue) = context_for("https://example.com/other", RobotsAndSitemap);
+        service.oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn oversized_rules_fail_open() {
+        let client = StaticClient::new("/robots.txt", ROBOTS);
+        let service = ExcludeLayer::new()
+            .with_max_size(16)
+            .layer(tower::service_fn(|_cx| async {
+                Ok::<_, std::convert::Infallible>(Signal::Continue)
+            }));
+
+        // The rules file exceeds the cap, so even disallowed paths pass.
+        let (cx, _queue) = context_for("https://example.com/private/page", client);
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+
+    #[tokio::test]
+    async fn fails_open_without_rules() {
+        // `Noop` answers every request, including `robots.txt`, with an
+        // empty body, so every path must stay allowed.
+        let service = ExcludeLayer::new().layer(tower::service_fn(|_cx| async {
+            Ok::<_, std::convert::Infallible>(Signal::Continue)
+        }));
+
+        let (cx, _queue) = context_for("https://example.com/anything", Noop::new());
+        let signal = service.oneshot(cx).await.unwrap();
+        assert!(matches!(signal, Signal::Continue));
+    }
+}
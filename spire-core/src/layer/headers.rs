@@ -0,0 +1,228 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use http::header::{HeaderName, HeaderValue, ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, USER_AGENT};
+use tower::{Layer, Service};
+
+use crate::backend::Client;
+use crate::context::Context;
+use crate::signal::Signal;
+
+/// Layer injecting a consistent, realistic set of browser headers.
+///
+/// Requests sent with only a bare `User-Agent` — or none at all — are an
+/// easy tell for anti-bot systems; real browsers send a stable bundle of
+/// `Accept`, `Accept-Language`, `Accept-Encoding` and `Sec-Fetch-*`
+/// headers alongside it. The profiles here mirror those bundles so an
+/// HTTP-only crawl blends in without a browser backend. A header already
+/// present on a request always wins — the layer only fills headers in,
+/// so per-request overrides need no escape hatch.
+///
+/// The headers are plain request decoration, which is why this is a
+/// layer and not a backend concern: it composes with any backend, though
+/// it mostly matters for HTTP clients — a browser sends the real thing.
+#[derive(Debug, Clone)]
+pub struct BrowserHeadersLayer {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// `Sec-Fetch-*` values of a top-level document navigation, shared by
+/// every profile.
+const SEC_FETCH: [(&str, &str); 4] = [
+    ("sec-fetch-dest", "document"),
+    ("sec-fetch-mode", "navigate"),
+    ("sec-fetch-site", "none"),
+    ("sec-fetch-user", "?1"),
+];
+
+impl BrowserHeadersLayer {
+    /// Creates a layer mimicking a desktop Chrome navigation.
+    pub fn chrome() -> Self {
+        Self::profile(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+             (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36",
+            "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,\
+             image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7",
+            "en-US,en;q=0.9",
+        )
+    }
+
+    /// Creates a layer mimicking a desktop Firefox navigation.
+    pub fn firefox() -> Self {
+        Self::profile(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:128.0) \
+             Gecko/20100101 Firefox/128.0",
+            "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,\
+             image/webp,image/png,image/svg+xml,*/*;q=0.8",
+            "en-US,en;q=0.5",
+        )
+    }
+
+    /// Creates a layer sending exactly the given headers.
+    pub fn custom(headers: impl IntoIterator<Item = (HeaderName, HeaderValue)>) -> Self {
+        Self {
+            headers: headers.into_iter().collect(),
+        }
+    }
+
+    /// Adds a header to the injected set, replacing a profile value of
+    /// the same name — e.g. a different `Accept-Language` on top of
+    /// [`BrowserHeadersLayer::chrome`].
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.retain(|(existing, _)| *existing != name);
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Assembles the shared header bundle around profile-specific values.
+    fn profile(user_agent: &'static str, accept: &'static str, language: &'static str) -> Self {
+        let mut headers = vec![
+            (USER_AGENT, HeaderValue::from_static(user_agent)),
+            (ACCEPT, HeaderValue::from_static(accept)),
+            (ACCEPT_LANGUAGE, HeaderValue::from_static(language)),
+            (ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate, br, zstd")),
+            (
+                HeaderName::from_static("upgrade-insecure-requests"),
+                HeaderValue::from_static("1"),
+            ),
+        ];
+        for (name, value) in SEC_FETCH {
+            headers.push((
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            ));
+        }
+
+        Self { headers }
+    }
+}
+
+impl<S> Layer<S> for BrowserHeadersLayer {
+    type Service = BrowserHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BrowserHeaders {
+            inner,
+            headers: self.headers.clone(),
+        }
+    }
+}
+
+/// Middleware service produced by [`BrowserHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct BrowserHeaders<S> {
+    inner: S,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl<C, S> Service<Context<C>> for BrowserHeaders<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let injected = self.headers.clone();
+
+        Box::pin(async move {
+            if let Some(request) = cx.get_mut() {
+                let headers = request.headers_mut();
+                for (name, value) in injected {
+                    if !headers.contains_key(&name) {
+                        headers.insert(name, value);
+                    }
+                }
+            }
+
+            inner.call(cx).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::context_for;
+
+    type Seen = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// Records every request header seen by the inner service.
+    macro_rules! observing_service {
+        ($seen:expr) => {{
+            let seen = $seen.clone();
+            tower::service_fn(move |cx: Context<Noop>| {
+                let seen = seen.clone();
+                async move {
+                    if let Some(request) = cx.request() {
+                        let mut guard = seen.lock().unwrap();
+                        for (name, value) in request.headers() {
+                            guard.push((name.to_string(), value.to_str().unwrap().to_owned()));
+                        }
+                    }
+                    Ok::<_, Infallible>(Signal::Continue)
+                }
+            })
+        }};
+    }
+
+    #[tokio::test]
+    async fn profile_injects_the_full_bundle() {
+        let seen: Seen = Arc::default();
+        let service = BrowserHeadersLayer::chrome().layer(observing_service!(seen));
+
+        let (cx, _queue) = context_for("https://example.com/", Noop::new());
+        service.oneshot(cx).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        let value = |name: &str| {
+            seen.iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert!(value("user-agent").unwrap().contains("Chrome/"));
+        assert!(value("accept").unwrap().starts_with("text/html"));
+        assert_eq!(value("sec-fetch-mode").as_deref(), Some("navigate"));
+        assert_eq!(value("upgrade-insecure-requests").as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn present_headers_and_overrides_win() {
+        let seen: Seen = Arc::default();
+        let service = BrowserHeadersLayer::firefox()
+            .with_header(ACCEPT_LANGUAGE, HeaderValue::from_static("de-DE,de;q=0.9"))
+            .layer(observing_service!(seen));
+
+        let (mut cx, _queue) = context_for("https://example.com/", Noop::new());
+        cx.get_mut()
+            .unwrap()
+            .headers_mut()
+            .insert(USER_AGENT, HeaderValue::from_static("custom-bot/1.0"));
+        service.oneshot(cx).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        let value = |name: &str| {
+            seen.iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(value("user-agent").as_deref(), Some("custom-bot/1.0"));
+        assert_eq!(value("accept-language").as_deref(), Some("de-DE,de;q=0.9"));
+    }
+}
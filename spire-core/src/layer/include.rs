@@ -0,0 +1,454 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tower::{Layer, Service};
+
+use crate::backend::Client;
+use crate::context::{Context, Lastmod, RequestQueue, Tag, Task};
+use crate::layer::{fetch_text, FetchLimits};
+use crate::signal::Signal;
+
+/// Default bounds on a `sitemap.xml` fetch: 30 seconds and 50 MiB, the
+/// size limit of the sitemap protocol.
+pub(crate) const SITEMAP_LIMITS: FetchLimits = FetchLimits {
+    timeout: Duration::from_secs(30),
+    max_size: 50 * 1024 * 1024,
+};
+
+/// Layer seeding the request queue from each host's `sitemap.xml`.
+///
+/// The first request for a host fetches `sitemap.xml` through the backend
+/// client of that request and enqueues every `<loc>` entry under
+/// [`Tag::Fallback`] (or the tag set by [`IncludeLayer::with_tag`]);
+/// afterwards the host is marked seeded and requests pass through
+/// untouched. Fetch failures are logged and the host is
+/// still marked seeded, so a missing sitemap costs one lookup per host.
+///
+/// Entries carrying a `<lastmod>` get it stamped onto the enqueued
+/// [`Task`] as a [`Lastmod`] extension; combined with
+/// [`IncludeLayer::with_since`] this turns a full re-crawl into an
+/// incremental one.
+#[derive(Debug, Clone)]
+pub struct IncludeLayer {
+    seeded: Arc<Mutex<HashSet<String>>>,
+    since: Option<SystemTime>,
+    tag: Tag,
+    limits: FetchLimits,
+}
+
+impl IncludeLayer {
+    /// Creates a new sitemap seeding layer.
+    pub fn new() -> Self {
+        Self {
+            seeded: Arc::default(),
+            since: None,
+            tag: Tag::Fallback,
+            limits: SITEMAP_LIMITS,
+        }
+    }
+
+    /// Skips sitemap entries whose `<lastmod>` is older than the given
+    /// time — typically the end of the previous crawl.
+    ///
+    /// Entries without a `<lastmod>` are always enqueued, since nothing
+    /// proves them unchanged.
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Enqueues sitemap entries under the given tag instead of
+    /// [`Tag::Fallback`].
+    ///
+    /// This overrides the tag the entries would otherwise inherit, letting
+    /// a dedicated handler treat bulk-seeded URLs differently from
+    /// link-discovered ones.
+    pub fn with_tag(mut self, tag: Tag) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Caps the time spent fetching a `sitemap.xml` file.
+    ///
+    /// A fetch exceeding the cap is logged and the host stays unseeded;
+    /// defaults to 30 seconds.
+    pub fn with_fetch_timeout(mut self, timeout: Duration) -> Self {
+        self.limits.timeout = timeout;
+        self
+    }
+
+    /// Caps the size of an accepted `sitemap.xml` file in bytes.
+    ///
+    /// A larger file is logged and seeds nothing; defaults to 50 MiB,
+    /// the size limit of the sitemap protocol.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.limits.max_size = max_size;
+        self
+    }
+}
+
+impl Default for IncludeLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for IncludeLayer {
+    type Service = Include<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Include {
+            inner,
+            seeded: self.seeded.clone(),
+            since: self.since,
+            tag: self.tag.clone(),
+            limits: self.limits,
+        }
+    }
+}
+
+/// Middleware service produced by [`IncludeLayer`].
+#[derive(Debug, Clone)]
+pub struct Include<S> {
+    inner: S,
+    seeded: Arc<Mutex<HashSet<String>>>,
+    since: Option<SystemTime>,
+    tag: Tag,
+    limits: FetchLimits,
+}
+
+impl<C, S> Service<Context<C>> for Include<S>
+where
+    C: Client,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Signal;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Signal, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let seeded = self.seeded.clone();
+        let since = self.since;
+        let tag = self.tag.clone();
+        let limits = self.limits;
+
+        Box::pin(async move {
+            let uri = cx.uri().clone();
+            if let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) {
+                let origin = format!("{scheme}://{authority}");
+                let first = seeded.lock().unwrap().insert(origin.clone());
+                if first {
+                    seed_from_sitemap(&mut cx, &origin, since, &tag, limits).await;
+                }
+            }
+
+            inner.call(cx).await
+        })
+    }
+}
+
+/// Fetches `sitemap.xml` and enqueues every listed URL, skipping entries
+/// unchanged since the configured cutoff.
+async fn seed_from_sitemap<C: Client>(
+    cx: &mut Context<C>,
+    origin: &str,
+    since: Option<SystemTime>,
+    tag: &Tag,
+    limits: FetchLimits,
+) {
+    let url = format!("{origin}/sitemap.xml");
+    seed_from_sitemap_url(cx, &url, since, tag, limits).await;
+}
+
+/// Fetches the sitemap at `url` and enqueues every listed URL.
+///
+/// The seeding half of this layer, shared with the `Sitemap:` directive
+/// discovery of [`ExcludeLayer`](crate::layer::ExcludeLayer).
+pub(crate) async fn seed_from_sitemap_url<C: Client>(
+    cx: &mut Context<C>,
+    url: &str,
+    since: Option<SystemTime>,
+    tag: &Tag,
+    limits: FetchLimits,
+) {
+    let Some(text) = fetch_text(cx, url, "", limits).await else {
+        return;
+    };
+
+    let queue: RequestQueue = cx.queue();
+    for entry in parse_sitemap(&text) {
+        if let (Some(since), Some(lastmod)) = (since, entry.lastmod) {
+            if lastmod < since {
+                tracing::trace!(loc = %entry.loc, "skipping unchanged sitemap entry");
+                continue;
+            }
+        }
+
+        let result = match entry.lastmod {
+            // Stamping `Lastmod` needs a prepared task; plain entries keep
+            // going through the normalizer-aware append.
+            Some(lastmod) => match Task::builder(&entry.loc).with_tag(tag.clone()).build() {
+                Ok(mut task) => {
+                    task.request_mut().extensions_mut().insert(Lastmod(lastmod));
+                    queue.append_request(task).await
+                }
+                Err(error) => Err(error),
+            },
+            None => queue.append_with_tag(tag.clone(), &entry.loc).await,
+        };
+
+        if let Err(error) = result {
+            tracing::debug!(loc = %entry.loc, %error, "failed to enqueue sitemap entry");
+        }
+    }
+}
+
+/// A single `<url>` (or `<sitemap>`) entry of a sitemap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SitemapEntry {
+    pub(crate) loc: String,
+    pub(crate) lastmod: Option<SystemTime>,
+}
+
+/// Extracts the `<loc>` entries of a `<urlset>` or `<sitemapindex>`,
+/// together with the `<lastmod>` accompanying each location.
+pub(crate) fn parse_sitemap(text: &str) -> Vec<SitemapEntry> {
+    let mut entries = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("<loc>") {
+        rest = &rest[start + "<loc>".len()..];
+        let Some(end) = rest.find("</loc>") else {
+            break;
+        };
+
+        let loc = rest[..end].trim();
+        rest = &rest[end + "</loc>".len()..];
+
+        // The accompanying `<lastmod>` sits between this location and the
+        // next one.
+        let scope = match rest.find("<loc>") {
+            Some(next) => &rest[..next],
+            None => rest,
+        };
+        let lastmod = find_lastmod(scope).and_then(parse_w3c_datetime);
+
+        if !loc.is_empty() {
+            entries.push(SitemapEntry {
+                loc: loc.to_owned(),
+                lastmod,
+            });
+        }
+    }
+
+    entries
+}
+
+/// Returns the content of the first `<lastmod>` element, if any.
+fn find_lastmod(scope: &str) -> Option<&str> {
+    let start = scope.find("<lastmod>")? + "<lastmod>".len();
+    let end = scope[start..].find("</lastmod>")?;
+    Some(scope[start..start + end].trim())
+}
+
+/// Parses the W3C datetime profile used by `<lastmod>`: `YYYY-MM-DD`,
+/// optionally followed by `Thh:mm:ss` (fractional seconds are ignored)
+/// and a `Z` or `±hh:mm` offset.
+pub(crate) fn parse_w3c_datetime(text: &str) -> Option<SystemTime> {
+    let text = text.trim();
+    let (date, time) = match text.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut seconds = days_from_civil(year, month, day).checked_mul(86_400)?;
+    if let Some(time) = time {
+        let (clock, offset) = split_utc_offset(time)?;
+        let mut clock = clock.splitn(3, ':');
+        let hours: i64 = clock.next()?.parse().ok()?;
+        let minutes: i64 = clock.next()?.parse().ok()?;
+        let whole = clock.next().unwrap_or("0");
+        let secs: i64 = whole.split('.').next()?.parse().ok()?;
+
+        seconds += hours * 3_600 + minutes * 60 + secs - offset;
+    }
+
+    let seconds = u64::try_from(seconds).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Splits a `<lastmod>` time into its clock and UTC offset in seconds.
+fn split_utc_offset(time: &str) -> Option<(&str, i64)> {
+    if let Some(clock) = time.strip_suffix('Z') {
+        return Some((clock, 0));
+    }
+
+    let Some(at) = time.rfind(['+', '-']) else {
+        return Some((time, 0));
+    };
+
+    let (clock, offset) = time.split_at(at);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let (hours, minutes) = offset[1..].split_once(':')?;
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    Some((clock, sign * (hours * 3_600 + minutes * 60)))
+}
+
+/// Days between the given civil date and 1970-01-01, proleptic Gregorian.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod test {
+    use tower::{Layer, ServiceExt};
+
+    use super::*;
+    use crate::test_utils::{context_for, StaticClient};
+
+    const SITEMAP: &str = "\
+        <?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+          <url><loc>https://example.com/a</loc></url>\n\
+          <url><loc> https://example.com/b </loc></url>\n\
+        </urlset>";
+
+    const DATED_SITEMAP: &str = "\
+        <?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+          <url><loc>https://example.com/old</loc><lastmod>2020-01-01</lastmod></url>\n\
+          <url><loc>https://example.com/new</loc><lastmod>2024-06-01T12:00:00Z</lastmod></url>\n\
+          <url><loc>https://example.com/undated</loc></url>\n\
+        </urlset>";
+
+    #[test]
+    fn parses_urlset_locations() {
+        let entries = parse_sitemap(SITEMAP);
+        let locs: Vec<_> = entries.iter().map(|entry| entry.loc.as_str()).collect();
+        assert_eq!(locs, vec!["https://example.com/a", "https://example.com/b"]);
+        assert!(entries.iter().all(|entry| entry.lastmod.is_none()));
+    }
+
+    #[test]
+    fn parses_lastmod_per_location() {
+        let entries = parse_sitemap(DATED_SITEMAP);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].lastmod, parse_w3c_datetime("2020-01-01"));
+        assert!(entries[1].lastmod > entries[0].lastmod);
+        assert_eq!(entries[2].lastmod, None);
+    }
+
+    #[test]
+    fn parses_w3c_datetime_forms() {
+        let date = parse_w3c_datetime("2024-01-15").unwrap();
+        assert_eq!(date, UNIX_EPOCH + Duration::from_secs(1_705_276_800));
+
+        let full = parse_w3c_datetime("2024-01-15T01:30:00+01:30").unwrap();
+        assert_eq!(full, UNIX_EPOCH + Duration::from_secs(1_705_276_800));
+
+        assert_eq!(parse_w3c_datetime("2024-01-15T00:00:00.5Z"), Some(date));
+        assert_eq!(parse_w3c_datetime("not a date"), None);
+        assert_eq!(parse_w3c_datetime("2024-13-01"), None);
+    }
+
+    #[test]
+    fn tolerates_markup_noise() {
+        assert!(parse_sitemap("<urlset><loc></loc><loc>x").is_empty());
+        assert!(parse_sitemap("no sitemap here").is_empty());
+    }
+
+    #[tokio::test]
+    async fn seeds_queue_once_per_host() {
+        let client = StaticClient::new("/sitemap.xml", SITEMAP);
+        let service = IncludeLayer::new().layer(tower::service_fn(|_cx| async {
+            Ok::<_, std::convert::Infallible>(Signal::Continue)
+        }));
+
+        let (cx, queue) = context_for("https://example.com/", client.clone());
+        service.clone().oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 2);
+
+        // The same host is already seeded; nothing is enqueued again.
+        let (cx, _queue) = context_for("https://example.com/other", client);
+        service.oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn custom_tag_overrides_the_fallback() {
+        let client = StaticClient::new("/sitemap.xml", SITEMAP);
+        let service = IncludeLayer::new()
+            .with_tag(Tag::from("sitemap"))
+            .layer(tower::service_fn(|_cx| async {
+                Ok::<_, std::convert::Infallible>(Signal::Continue)
+            }));
+
+        let (cx, queue) = context_for("https://example.com/", client);
+        service.oneshot(cx).await.unwrap();
+
+        let entries = queue.read_all().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|task| *task.tag() == Tag::from("sitemap")));
+    }
+
+    #[tokio::test]
+    async fn oversized_sitemap_seeds_nothing() {
+        let client = StaticClient::new("/sitemap.xml", SITEMAP);
+        let service = IncludeLayer::new()
+            .with_max_size(16)
+            .layer(tower::service_fn(|_cx| async {
+                Ok::<_, std::convert::Infallible>(Signal::Continue)
+            }));
+
+        let (cx, queue) = context_for("https://example.com/", client);
+        service.oneshot(cx).await.unwrap();
+        assert_eq!(queue.len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn since_filter_skips_unchanged_entries() {
+        let client = StaticClient::new("/sitemap.xml", DATED_SITEMAP);
+        let since = parse_w3c_datetime("2022-01-01").unwrap();
+        let service = IncludeLayer::new().with_since(since).layer(tower::service_fn(
+            |_cx| async { Ok::<_, std::convert::Infallible>(Signal::Continue) },
+        ));
+
+        let (cx, queue) = context_for("https://example.com/", client);
+        service.oneshot(cx).await.unwrap();
+
+        // `/old` predates the cutoff; `/new` carries its lastmod onto the
+        // task and `/undated` is enqueued unconditionally.
+        let mut entries = queue.read_all().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        let new = entries.remove(0);
+        assert_eq!(new.uri().to_string(), "https://example.com/new");
+        assert!(new.lastmod().is_some_and(|lastmod| lastmod > since));
+        assert!(entries[0].lastmod().is_none());
+    }
+}
@@ -0,0 +1,92 @@
+//! Politeness middleware applying before a route runs.
+//!
+//! Both layers fetch their auxiliary documents (`robots.txt`,
+//! `sitemap.xml`) through the [`Client`] the backend provided for the
+//! request, so they behave the same on every backend — including
+//! browser-based ones.
+//!
+//! [`Client`]: crate::backend::Client
+
+mod accept;
+mod backoff;
+mod dedup;
+mod exclude;
+mod headers;
+mod include;
+
+pub use accept::{Accept, AcceptLayer};
+pub use backoff::{Backoff, BackoffLayer};
+pub use dedup::{Dedup, DedupLayer};
+pub use exclude::{Exclude, ExcludeLayer};
+pub use headers::{BrowserHeaders, BrowserHeadersLayer};
+pub use include::{Include, IncludeLayer};
+
+use std::time::Duration;
+
+use crate::context::{Body, Context, Request, Response};
+use crate::backend::Client;
+
+/// Bounds on an auxiliary document fetch.
+///
+/// An adversarial host can stall the connection or serve an arbitrarily
+/// large file; each layer caps both with defaults of its own and exposes
+/// builder methods to adjust them.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FetchLimits {
+    pub(crate) timeout: Duration,
+    pub(crate) max_size: usize,
+}
+
+/// Resolves `{origin}{path}` through the context's backend client.
+///
+/// Returns the body as text when the response has a success status and
+/// fits within `limits`, and `None` otherwise; all failures are logged
+/// and swallowed, since both layers treat a missing auxiliary document
+/// as a non-event.
+async fn fetch_text<C: Client>(
+    cx: &mut Context<C>,
+    origin: &str,
+    path: &str,
+    limits: FetchLimits,
+) -> Option<String> {
+    let url = format!("{origin}{path}");
+    let request: Request = match http::Request::get(&url).body(Body::empty()) {
+        Ok(request) => request,
+        Err(error) => {
+            tracing::debug!(%url, %error, "failed to build auxiliary request");
+            return None;
+        }
+    };
+
+    let response: Response = match tokio::time::timeout(limits.timeout, cx.resolve_request(request))
+        .await
+    {
+        Ok(Ok(response)) => response,
+        Ok(Err(error)) => {
+            tracing::debug!(%url, %error, "failed to fetch auxiliary document");
+            return None;
+        }
+        Err(_) => {
+            tracing::warn!(%url, timeout = ?limits.timeout, "auxiliary fetch timed out");
+            return None;
+        }
+    };
+
+    if !response.status().is_success() {
+        tracing::debug!(%url, status = %response.status(), "no usable auxiliary document");
+        return None;
+    }
+
+    let bytes = response.into_body().into_bytes();
+    if bytes.len() > limits.max_size {
+        tracing::warn!(
+            %url,
+            size = bytes.len(),
+            limit = limits.max_size,
+            "auxiliary document exceeds the size limit"
+        );
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
@@ -0,0 +1,33 @@
+//! Core building blocks of the [`spire`] crawling framework.
+//!
+//! This crate defines the backend abstraction, the per-request
+//! [`Context`], tag-based routing, the dataset storage layer and the run
+//! loop tying them together. It contains no protocol implementation: HTTP
+//! and browser backends live in the `spire-reqwest` and `spire-webdriver`
+//! crates.
+//!
+//! [`spire`]: https://docs.rs/spire
+//! [`Context`]: crate::context::Context
+
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub mod backend;
+pub mod client;
+pub mod context;
+pub mod dataset;
+pub mod extract;
+pub mod handler;
+pub mod layer;
+pub mod routing;
+
+mod error;
+mod signal;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
+
+pub use crate::client::{Client, CrawlEvent, CrawlOutcome, EventStream, Traversal};
+pub use crate::error::{BoxError, Error, ErrorKind, Result};
+pub use crate::routing::Router;
+pub use crate::signal::{CancelToken, IntoSignal, Signal, SignalCounts, SignalStats};
@@ -0,0 +1,297 @@
+//! Tag-based routing of contexts to handlers.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+
+use tower::util::BoxCloneService;
+use tower::{Layer, Service, ServiceExt};
+
+use crate::context::{Context, Tag};
+use crate::handler::{Handler, HandlerService};
+use crate::signal::Signal;
+use crate::Error;
+
+/// The type-erased service stored for each route.
+pub(crate) type Route<C> = BoxCloneService<Context<C>, Signal, Infallible>;
+
+/// Dispatches each incoming [`Context`] to the handler registered for its
+/// [`Tag`].
+///
+/// A router is built once, given to a [`Client`], and cloned into every
+/// worker. Contexts whose tag has no route fall back to the handler
+/// registered with [`Router::fallback`]; without one the request resolves
+/// to [`Signal::Skip`].
+///
+/// [`Client`]: crate::client::Client
+pub struct Router<C, S = ()> {
+    routes: HashMap<Tag, Route<C>>,
+    fallback: Option<Route<C>>,
+    state: S,
+}
+
+impl<C> Router<C, ()>
+where
+    C: Send + 'static,
+{
+    /// Creates an empty router with the unit state.
+    pub fn new() -> Self {
+        Self::with_state(())
+    }
+}
+
+impl<C> Default for Router<C, ()>
+where
+    C: Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, S> Router<C, S>
+where
+    C: Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates an empty router carrying the given state.
+    ///
+    /// The state is cloned into every handler registered afterwards and is
+    /// reachable through the [`State`] extractor.
+    ///
+    /// [`State`]: crate::extract::State
+    pub fn with_state(state: S) -> Self {
+        Self {
+            routes: HashMap::new(),
+            fallback: None,
+            state,
+        }
+    }
+
+    /// Registers a handler for the given tag.
+    pub fn route<H, T>(mut self, tag: impl Into<Tag>, handler: H) -> Self
+    where
+        H: Handler<T, C, S>,
+        T: 'static,
+    {
+        let service = HandlerService::new(handler, self.state.clone());
+        self.routes.insert(tag.into(), BoxCloneService::new(service));
+        self
+    }
+
+    /// Registers an arbitrary Tower [`Service`] for the given tag.
+    ///
+    /// This is the escape hatch for handlers pre-composed with middleware,
+    /// e.g. via [`Handler::into_service`] and a `ServiceBuilder` stack.
+    pub fn route_service<Svc>(mut self, tag: impl Into<Tag>, service: Svc) -> Self
+    where
+        Svc: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+        Svc::Error: Into<Infallible>,
+        Svc::Future: Send + 'static,
+    {
+        let service = service.map_err(|error| -> Infallible { error.into() });
+        self.routes.insert(tag.into(), BoxCloneService::new(service));
+        self
+    }
+
+    /// Registers the handler receiving contexts with an unmatched tag.
+    pub fn fallback<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, C, S>,
+        T: 'static,
+    {
+        let service = HandlerService::new(handler, self.state.clone());
+        self.fallback = Some(BoxCloneService::new(service));
+        self
+    }
+
+    /// Wraps every currently registered route (and fallback) in the given
+    /// [`Layer`].
+    ///
+    /// Routes registered after this call are not affected.
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<C>>,
+        L::Service: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+        <L::Service as Service<Context<C>>>::Error: Into<Infallible>,
+        <L::Service as Service<Context<C>>>::Future: Send + 'static,
+    {
+        let wrap = |route: Route<C>| {
+            let service = layer
+                .layer(route)
+                .map_err(|error| -> Infallible { error.into() });
+            BoxCloneService::new(service)
+        };
+
+        self.routes = self
+            .routes
+            .drain()
+            .map(|(tag, route)| (tag, wrap(route)))
+            .collect();
+        self.fallback = self.fallback.take().map(wrap);
+        self
+    }
+
+    /// Wraps every currently registered route (and fallback) in the given
+    /// [`Layer`], converting middleware errors into [`Signal::Fail`].
+    ///
+    /// [`Router::layer`] requires infallible services, which rules out
+    /// standard Tower middleware with real error types — timeouts,
+    /// retries, load shedding. This variant accepts any service error
+    /// converting into [`Error`] and turns it into flow control: the
+    /// failing request resolves to [`Signal::Fail`] while the crawl
+    /// keeps running. Routes registered after this call are not
+    /// affected.
+    pub fn layer_fallible<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route<C>>,
+        L::Service: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+        <L::Service as Service<Context<C>>>::Error: Into<Error>,
+        <L::Service as Service<Context<C>>>::Future: Send + 'static,
+    {
+        let wrap = |route: Route<C>| {
+            let service = CatchError {
+                inner: layer.layer(route),
+            };
+            BoxCloneService::new(service)
+        };
+
+        self.routes = self
+            .routes
+            .drain()
+            .map(|(tag, route)| (tag, wrap(route)))
+            .collect();
+        self.fallback = self.fallback.take().map(wrap);
+        self
+    }
+
+    /// Discards the state, keeping the erased routing table.
+    pub(crate) fn into_routes(self) -> Routes<C> {
+        Routes {
+            routes: self.routes,
+            fallback: self.fallback,
+        }
+    }
+}
+
+impl<C, S: Clone> Clone for Router<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+            fallback: self.fallback.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<C, S> fmt::Debug for Router<C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("routes", &self.routes.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Adapter turning the error of a fallible middleware stack into
+/// [`Signal::Fail`]; see [`Router::layer_fallible`].
+#[derive(Clone)]
+struct CatchError<S> {
+    inner: S,
+}
+
+impl<C, S> Service<Context<C>> for CatchError<S>
+where
+    C: Send + 'static,
+    S: Service<Context<C>, Response = Signal> + Clone + Send + 'static,
+    S::Error: Into<Error>,
+    S::Future: Send + 'static,
+{
+    type Response = Signal;
+    type Error = Infallible;
+    type Future =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Result<Signal, Infallible>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        // Readiness errors surface through `oneshot` in `call`, so this
+        // adapter itself is always ready.
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, cx: Context<C>) -> Self::Future {
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            Ok(match inner.oneshot(cx).await {
+                Ok(signal) => signal,
+                Err(error) => Signal::Fail(error.into()),
+            })
+        })
+    }
+}
+
+/// The erased routing table of a [`Router`], as stored by the client.
+pub(crate) struct Routes<C> {
+    routes: HashMap<Tag, Route<C>>,
+    fallback: Option<Route<C>>,
+}
+
+impl<C> Routes<C> {
+    /// Returns the route serving the given tag, if any.
+    pub(crate) fn find(&self, tag: &Tag) -> Option<Route<C>> {
+        self.routes.get(tag).or(self.fallback.as_ref()).cloned()
+    }
+}
+
+impl<C> Clone for Routes<C> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+impl<C> fmt::Debug for Routes<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Routes")
+            .field("routes", &self.routes.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tower::timeout::TimeoutLayer;
+
+    use super::*;
+    use crate::backend::utils::Noop;
+    use crate::test_utils::context_for;
+    use crate::ErrorKind;
+
+    async fn slow() {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fallible_layer_turns_errors_into_fail() {
+        let router: Router<Noop> = Router::new()
+            .route("slow", slow)
+            .layer_fallible(TimeoutLayer::new(Duration::from_millis(10)));
+
+        let routes = router.into_routes();
+        let route = routes.find(&Tag::from("slow")).unwrap();
+        let (cx, _queue) = context_for("https://example.com/", Noop::new());
+
+        let signal = route.oneshot(cx).await.unwrap();
+        let Signal::Fail(error) = signal else {
+            panic!("expected a failure, got {signal:?}");
+        };
+        assert_eq!(error.kind(), ErrorKind::Context);
+    }
+}
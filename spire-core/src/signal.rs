@@ -0,0 +1,267 @@
+//! Flow-control signals produced while processing requests.
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Error;
+
+/// Outcome of processing a single queued request.
+///
+/// Handlers do not return a `Signal` directly; their return value is
+/// converted through [`IntoSignal`] by the routing machinery.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Signal {
+    /// Processing finished normally; the crawl carries on.
+    Continue,
+    /// The request was skipped before or during handling.
+    Skip,
+    /// Back off for the given duration before dispatching more work.
+    Wait(Duration),
+    /// The request failed with an error.
+    Fail(Error),
+    /// The crawl found what it was looking for; finish it gracefully.
+    ///
+    /// The runner stops dispatching queued tasks, waits out the requests
+    /// already in flight and returns `Ok`. Contrast with [`Signal::Skip`],
+    /// which drops only the current request while the crawl carries on,
+    /// and with [`Signal::Fail`], which records an error (and may cancel
+    /// matching queued tasks) but likewise does not end the run.
+    Stop,
+}
+
+impl Signal {
+    /// Returns `true` for [`Signal::Fail`].
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Signal::Fail(_))
+    }
+
+    /// Returns `true` for [`Signal::Stop`].
+    pub fn is_stop(&self) -> bool {
+        matches!(self, Signal::Stop)
+    }
+}
+
+/// Conversion of handler return values into a [`Signal`].
+pub trait IntoSignal {
+    /// Performs the conversion.
+    fn into_signal(self) -> Signal;
+}
+
+impl IntoSignal for Signal {
+    fn into_signal(self) -> Signal {
+        self
+    }
+}
+
+impl IntoSignal for () {
+    fn into_signal(self) -> Signal {
+        Signal::Continue
+    }
+}
+
+impl IntoSignal for Error {
+    fn into_signal(self) -> Signal {
+        Signal::Fail(self)
+    }
+}
+
+impl<T, E> IntoSignal for Result<T, E>
+where
+    T: IntoSignal,
+    E: Into<Error>,
+{
+    fn into_signal(self) -> Signal {
+        match self {
+            Ok(value) => value.into_signal(),
+            Err(error) => Signal::Fail(error.into()),
+        }
+    }
+}
+
+/// Cooperative cancellation handle tied to the crawl's shutdown signal.
+///
+/// Extractable in handlers, where it lets long operations abort once
+/// [`Client::run_with_shutdown`] observes its shutdown future — e.g. by
+/// `select!`-ing a slow wait against [`CancelToken::cancelled`]. The
+/// token never resolves during a crawl driven by [`Client::run`] or one
+/// that drains its queue before the signal fires.
+///
+/// [`Client::run`]: crate::client::Client::run
+/// [`Client::run_with_shutdown`]: crate::client::Client::run_with_shutdown
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    inner: tokio::sync::watch::Receiver<bool>,
+}
+
+impl CancelToken {
+    /// Creates a token and the sender side that fires it.
+    pub(crate) fn channel() -> (tokio::sync::watch::Sender<bool>, Self) {
+        let (sender, receiver) = tokio::sync::watch::channel(false);
+        (sender, Self { inner: receiver })
+    }
+
+    /// Returns `true` once the shutdown signal has fired.
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.borrow()
+    }
+
+    /// Resolves once the shutdown signal fires.
+    ///
+    /// Pends forever when it never does, so it is safe to `select!`
+    /// against in a crawl that finishes normally.
+    pub async fn cancelled(&self) {
+        let mut inner = self.inner.clone();
+        if inner.wait_for(|cancelled| *cancelled).await.is_err() {
+            // The sender was dropped without firing: the run loop ended
+            // normally, so the handler must not observe a cancellation.
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Rolling window of recent [`Signal`] outcomes recorded by the runner.
+///
+/// Extractable in handlers, where it enables adaptive crawling: a handler
+/// can check [`SignalStats::error_rate`] and stop enqueueing follow-up
+/// work (or return [`Signal::Wait`]) while the rate spikes. The window is
+/// a fixed-size ring of atomics, so recording and reading never take a
+/// lock; counts are approximate under concurrency, which is fine for the
+/// trend-level decisions they inform.
+#[derive(Debug, Clone)]
+pub struct SignalStats {
+    inner: Arc<StatsInner>,
+}
+
+#[derive(Debug)]
+struct StatsInner {
+    slots: Vec<AtomicU8>,
+    cursor: AtomicUsize,
+}
+
+/// Slot encoding of an outcome; `EMPTY` marks never-written slots.
+const EMPTY: u8 = 0;
+const CONTINUE: u8 = 1;
+const SKIP: u8 = 2;
+const WAIT: u8 = 3;
+const FAIL: u8 = 4;
+const STOP: u8 = 5;
+
+/// Counts of the outcomes currently in a [`SignalStats`] window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignalCounts {
+    /// Requests that finished normally.
+    pub continued: usize,
+    /// Requests that were skipped.
+    pub skipped: usize,
+    /// Requests that asked the crawl to back off.
+    pub waited: usize,
+    /// Requests that failed.
+    pub failed: usize,
+    /// Requests that asked the crawl to finish.
+    pub stopped: usize,
+}
+
+impl SignalCounts {
+    /// Returns the number of outcomes in the window.
+    pub fn total(&self) -> usize {
+        self.continued + self.skipped + self.waited + self.failed + self.stopped
+    }
+}
+
+impl SignalStats {
+    /// Creates a window holding the given number of recent outcomes.
+    pub fn new(window: usize) -> Self {
+        let slots = std::iter::repeat_with(|| AtomicU8::new(EMPTY))
+            .take(window.max(1))
+            .collect();
+
+        Self {
+            inner: Arc::new(StatsInner {
+                slots,
+                cursor: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Records the outcome of a processed request, evicting the oldest
+    /// entry once the window is full.
+    pub(crate) fn record(&self, signal: &Signal) {
+        let value = match signal {
+            Signal::Continue => CONTINUE,
+            Signal::Skip => SKIP,
+            Signal::Wait(_) => WAIT,
+            Signal::Fail(_) => FAIL,
+            Signal::Stop => STOP,
+        };
+
+        let cursor = self.inner.cursor.fetch_add(1, Ordering::Relaxed);
+        let slot = &self.inner.slots[cursor % self.inner.slots.len()];
+        slot.store(value, Ordering::Relaxed);
+    }
+
+    /// Returns the outcome counts currently in the window.
+    pub fn counts(&self) -> SignalCounts {
+        let mut counts = SignalCounts::default();
+        for slot in &self.inner.slots {
+            match slot.load(Ordering::Relaxed) {
+                CONTINUE => counts.continued += 1,
+                SKIP => counts.skipped += 1,
+                WAIT => counts.waited += 1,
+                FAIL => counts.failed += 1,
+                STOP => counts.stopped += 1,
+                _ => {}
+            }
+        }
+
+        counts
+    }
+
+    /// Returns the fraction of failed outcomes in the window, `0.0` while
+    /// the window is empty.
+    pub fn error_rate(&self) -> f64 {
+        let counts = self.counts();
+        match counts.total() {
+            0 => 0.0,
+            total => counts.failed as f64 / total as f64,
+        }
+    }
+}
+
+impl Default for SignalStats {
+    /// Creates a window of 64 outcomes.
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn window_evicts_oldest_outcomes() {
+        let stats = SignalStats::new(4);
+        for _ in 0..4 {
+            stats.record(&Signal::Fail(Error::msg(ErrorKind::Http, "boom")));
+        }
+        assert_eq!(stats.error_rate(), 1.0);
+
+        // Two successes push half of the failures out of the window.
+        stats.record(&Signal::Continue);
+        stats.record(&Signal::Continue);
+        let counts = stats.counts();
+        assert_eq!(counts.failed, 2);
+        assert_eq!(counts.continued, 2);
+        assert_eq!(stats.error_rate(), 0.5);
+    }
+
+    #[test]
+    fn empty_window_reports_zero() {
+        let stats = SignalStats::default();
+        assert_eq!(stats.counts().total(), 0);
+        assert_eq!(stats.error_rate(), 0.0);
+    }
+}
@@ -0,0 +1,60 @@
+//! Shared helpers for unit tests.
+
+use async_trait::async_trait;
+
+use crate::backend::Client;
+use crate::context::{Body, Context, QueueHooks, Request, Response, Task};
+use crate::dataset::{Data, DatasetRegistry, InMemDataset};
+
+/// Client answering one path with a canned body and everything else
+/// with an empty `200 OK`.
+#[derive(Debug, Clone)]
+pub(crate) struct StaticClient {
+    path: &'static str,
+    body: &'static str,
+}
+
+impl StaticClient {
+    pub(crate) fn new(path: &'static str, body: &'static str) -> Self {
+        Self { path, body }
+    }
+}
+
+#[async_trait]
+impl Client for StaticClient {
+    async fn resolve(&mut self, request: Request) -> crate::Result<Response> {
+        let body = match request.uri().path() == self.path {
+            true => Body::new(self.body),
+            false => Body::empty(),
+        };
+
+        Ok(http::Response::builder()
+            .status(http::StatusCode::OK)
+            .body(body)
+            .expect("static response should always build"))
+    }
+}
+
+/// Builds a context around the given URI and client, returning the
+/// backing queue for later inspection.
+pub(crate) fn context_for<C>(uri: &str, client: C) -> (Context<C>, Data<Task>) {
+    tagged_context_for(uri, crate::context::Tag::Fallback, client)
+}
+
+/// Like [`context_for`], with the task dispatched under the given tag.
+pub(crate) fn tagged_context_for<C>(
+    uri: &str,
+    tag: impl Into<crate::context::Tag>,
+    client: C,
+) -> (Context<C>, Data<Task>) {
+    let queue = Data::new(InMemDataset::queue());
+    let task = Task::builder(uri).with_tag(tag).build().expect("valid test uri");
+    let cx = Context::new(
+        task,
+        client,
+        queue.clone(),
+        DatasetRegistry::default(),
+        QueueHooks::default(),
+    );
+    (cx, queue)
+}
@@ -0,0 +1,54 @@
+//! Procedural macros for the [`spire`] crawler framework.
+//!
+//! [`spire`]: https://docs.rs/spire
+
+mod select;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives `spire::extract::Select` for a struct of scraped fields.
+///
+/// Each field carries a `#[select(...)]` attribute describing how its
+/// value is extracted from an HTML element:
+///
+/// - `css = "..."` — the CSS selector locating the field's element;
+/// - `attr = "..."` — read the given attribute instead of the text content;
+/// - `default = "..."` — the value to use when nothing matches, instead
+///   of an error (or `None` for `Option` fields);
+/// - `parse` — parse the text through [`FromStr`] into the field type,
+///   e.g. `f64` or `i64`, failing extraction when the value is malformed;
+/// - `trim`, `strip = "..."` — clean the text before use: trim
+///   whitespace and remove every occurrence of the given string;
+/// - `absolute` — resolve the value as a URL against the URL of the
+///   current request, turning relative `href`/`src` forms (including
+///   root- and protocol-relative ones) into absolute URLs.
+///
+/// [`FromStr`]: std::str::FromStr
+///
+/// Supported field types are `String`, `Option<String>`, `Vec<String>`
+/// and `Vec<T>` where `T` itself derives `Select`; a nested `Vec` runs
+/// `T`'s extraction within each element matching the field's selector and
+/// yields an empty `Vec` when nothing matches. A struct-level
+/// `#[select(css = "...")]` scopes the whole type to a repeated element,
+/// which the `Elements` extractor iterates.
+///
+/// ```ignore
+/// #[derive(Select)]
+/// #[select(css = ".quote")]
+/// struct Quote {
+///     #[select(css = ".text")]
+///     text: String,
+///     #[select(css = "a", attr = "href", absolute)]
+///     link: Option<String>,
+///     #[select(css = ".tag")]
+///     tags: Vec<String>,
+/// }
+/// ```
+#[proc_macro_derive(Select, attributes(select))]
+pub fn derive_select(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    select::expand(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
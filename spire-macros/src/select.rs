@@ -0,0 +1,403 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Parsed `#[select(...)]` attribute of a single field.
+struct FieldAttrs {
+    css: String,
+    attr: Option<String>,
+    attrs: bool,
+    default: Option<String>,
+    parse: bool,
+    trim: bool,
+    strip: Option<String>,
+    absolute: bool,
+}
+
+/// The shape of a field's type, deciding the generated extraction.
+enum FieldShape {
+    /// `String`: the first match is required.
+    One,
+    /// `Option<String>`: the first match, if any.
+    Maybe,
+    /// `Vec<String>`: every match.
+    Many,
+    /// `Vec<T>` where `T: Select`: run the nested extraction per match.
+    ManyNested(Box<syn::Type>),
+    /// `HashMap<String, String>` with `attrs`: every attribute of the
+    /// first match.
+    AttrMap,
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`#[derive(Select)]` only supports structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`#[derive(Select)]` only supports named fields",
+        ));
+    };
+
+    let ident = &input.ident;
+    let scope = parse_select_attrs(&input.attrs)?;
+    let selector = match scope {
+        Some(attrs) => {
+            let css = attrs.css;
+            quote! { ::std::option::Option::Some(#css) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+
+    let mut bindings = Vec::new();
+    let mut names = Vec::new();
+    for field in &fields.named {
+        let name = field.ident.as_ref().expect("named field has an ident");
+        let attrs = parse_select_attrs(&field.attrs)?.ok_or_else(|| {
+            Error::new_spanned(field, "field is missing its `#[select(...)]` attribute")
+        })?;
+        let shape = field_shape(&field.ty, attrs.parse, attrs.attrs)
+            .ok_or_else(|| Error::new_spanned(&field.ty, UNSUPPORTED_TYPE))?;
+        if attrs.attrs && !matches!(shape, FieldShape::AttrMap) {
+            return Err(Error::new_spanned(
+                field,
+                "`attrs` requires a `HashMap<String, String>` field",
+            ));
+        }
+        if matches!(shape, FieldShape::AttrMap)
+            && (attrs.attr.is_some()
+                || attrs.default.is_some()
+                || attrs.parse
+                || attrs.trim
+                || attrs.strip.is_some()
+                || attrs.absolute)
+        {
+            return Err(Error::new_spanned(
+                field,
+                "`attrs` captures every attribute verbatim; it cannot be combined with                  `attr`, `default`, `parse`, `trim`, `strip` or `absolute`",
+            ));
+        }
+        if matches!(shape, FieldShape::ManyNested(_))
+            && (attrs.attr.is_some() || attrs.trim || attrs.strip.is_some() || attrs.absolute)
+        {
+            return Err(Error::new_spanned(
+                field,
+                "`attr`, `trim`, `strip` and `absolute` cannot be combined with a nested \
+                 `Select` type",
+            ));
+        }
+        if matches!(shape, FieldShape::Many | FieldShape::ManyNested(_)) && attrs.default.is_some()
+        {
+            return Err(Error::new_spanned(
+                field,
+                "`default` is not supported on `Vec` fields; an empty match \
+                 already yields an empty `Vec`",
+            ));
+        }
+
+        bindings.push(expand_field(name, &attrs, &shape));
+        names.push(name);
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::spire::extract::Select for #ident {
+            fn selector() -> ::std::option::Option<&'static str> {
+                #selector
+            }
+
+            fn select(
+                element: &::spire::__private::ElementRef<'_>,
+            ) -> ::std::result::Result<Self, ::spire::extract::SelectError> {
+                Self::select_with_base(element, ::std::option::Option::None)
+            }
+
+            fn select_with_base(
+                element: &::spire::__private::ElementRef<'_>,
+                __base: ::std::option::Option<&::spire::__private::Url>,
+            ) -> ::std::result::Result<Self, ::spire::extract::SelectError> {
+                let _ = __base;
+                #(#bindings)*
+                ::std::result::Result::Ok(Self { #(#names,)* })
+            }
+        }
+    })
+}
+
+const UNSUPPORTED_TYPE: &str = "unsupported field type: expected `String`, `Option<String>`, \
+     `Vec<String>`, `Vec<T>` where `T` derives `Select`, or `HashMap<String, String>` with \
+     `attrs`";
+
+/// Parses a `#[select(css = "...", attr = "...", attrs, default = "...",
+/// parse, trim, strip = "...", absolute)]` attribute, if present.
+fn parse_select_attrs(attrs: &[syn::Attribute]) -> Result<Option<FieldAttrs>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("select")) else {
+        return Ok(None);
+    };
+
+    let mut css = None;
+    let mut target = None;
+    let mut attr_map = false;
+    let mut default = None;
+    let mut parse = false;
+    let mut trim = false;
+    let mut strip = None;
+    let mut absolute = false;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("css") {
+            css = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else if meta.path.is_ident("attr") {
+            target = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else if meta.path.is_ident("attrs") {
+            attr_map = true;
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            default = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else if meta.path.is_ident("parse") {
+            parse = true;
+            Ok(())
+        } else if meta.path.is_ident("trim") {
+            trim = true;
+            Ok(())
+        } else if meta.path.is_ident("strip") {
+            strip = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else if meta.path.is_ident("absolute") {
+            absolute = true;
+            Ok(())
+        } else {
+            Err(meta.error(
+                "expected `css`, `attr`, `attrs`, `default`, `parse`, `trim`, `strip` or \
+                 `absolute`",
+            ))
+        }
+    })?;
+
+    let css = css.ok_or_else(|| Error::new_spanned(attr, "missing `css = \"...\"`"))?;
+    Ok(Some(FieldAttrs {
+        css,
+        attr: target,
+        attrs: attr_map,
+        default,
+        parse,
+        trim,
+        strip,
+        absolute,
+    }))
+}
+
+/// Classifies the field type by its outermost path segment.
+///
+/// With `parse` the inner type only needs `FromStr`, so any type other
+/// than `Option` and `Vec` is treated as a single required value.
+fn field_shape(ty: &syn::Type, parse: bool, attrs: bool) -> Option<FieldShape> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+
+    match segment.ident.to_string().as_str() {
+        "HashMap" if attrs => Some(FieldShape::AttrMap),
+        "Option" => Some(FieldShape::Maybe),
+        "Vec" if parse => Some(FieldShape::Many),
+        "Vec" => match generic_arg(segment)? {
+            syn::Type::Path(inner) if inner.path.is_ident("String") => Some(FieldShape::Many),
+            inner => Some(FieldShape::ManyNested(Box::new(inner.clone()))),
+        },
+        "String" => Some(FieldShape::One),
+        _ if parse => Some(FieldShape::One),
+        _ => None,
+    }
+}
+
+/// Returns the single generic type argument of a segment, e.g. `T` in `Vec<T>`.
+fn generic_arg(segment: &syn::PathSegment) -> Option<&syn::Type> {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Generates the `let` binding extracting a single field.
+fn expand_field(name: &syn::Ident, attrs: &FieldAttrs, shape: &FieldShape) -> TokenStream {
+    let css = &attrs.css;
+    let field = name.to_string();
+
+    let value = match &attrs.attr {
+        Some(attr) => quote! {
+            found.attr(#attr).map(::std::borrow::ToOwned::to_owned)
+        },
+        None => quote! {
+            ::std::option::Option::Some(
+                found.text().collect::<::std::string::String>().trim().to_owned(),
+            )
+        },
+    };
+    let value = match cleanup(attrs) {
+        Some(cleanup) => quote! { (#value).map(#cleanup) },
+        None => value,
+    };
+    let value = match attrs.absolute {
+        true => quote! {
+            (#value).map(|raw| ::spire::extract::resolve_url(__base, raw))
+        },
+        false => value,
+    };
+
+    let parse_raw = quote! {
+        |raw: ::std::string::String| {
+            raw.parse()
+                .map_err(|_| ::spire::extract::SelectError::parse(#field, &raw))
+        }
+    };
+
+    let gather = if attrs.parse {
+        expand_parsed(shape, attrs, &value, &parse_raw, &field, css)
+    } else {
+        expand_plain(shape, attrs, &value, &field, css)
+    };
+
+    quote! {
+        let #name = {
+            let selector = ::spire::__private::Selector::parse(#css)
+                .map_err(|_| ::spire::extract::SelectError::selector(#css))?;
+            #[allow(unused_mut)]
+            let mut matches = element.select(&selector);
+            #gather
+        };
+    }
+}
+
+/// Generates the text-cleaning closure from `trim` and `strip`, if any.
+fn cleanup(attrs: &FieldAttrs) -> Option<TokenStream> {
+    let mut steps = TokenStream::new();
+    if attrs.trim {
+        steps.extend(quote! { let raw = raw.trim().to_owned(); });
+    }
+    if let Some(strip) = &attrs.strip {
+        steps.extend(quote! { let raw = raw.replace(#strip, ""); });
+    }
+
+    match steps.is_empty() {
+        true => None,
+        false => Some(quote! { |raw: ::std::string::String| { #steps raw } }),
+    }
+}
+
+/// Generates the gathering of a `parse` field through `FromStr`.
+fn expand_parsed(
+    shape: &FieldShape,
+    attrs: &FieldAttrs,
+    value: &TokenStream,
+    parse_raw: &TokenStream,
+    field: &str,
+    css: &str,
+) -> TokenStream {
+    match shape {
+        FieldShape::One => {
+            let fallback = match &attrs.default {
+                Some(default) => quote! { .unwrap_or_else(|| #default.to_owned()) },
+                None => quote! {
+                    .ok_or_else(|| ::spire::extract::SelectError::missing(#field, #css))?
+                },
+            };
+            quote! {
+                {
+                    let raw = matches.filter_map(|found| #value).next() #fallback;
+                    (#parse_raw)(raw)?
+                }
+            }
+        }
+        FieldShape::Maybe => {
+            let fallback = match &attrs.default {
+                Some(default) => quote! {
+                    .or_else(|| ::std::option::Option::Some(#default.to_owned()))
+                },
+                None => TokenStream::new(),
+            };
+            quote! {
+                matches
+                    .filter_map(|found| #value)
+                    .next()
+                    #fallback
+                    .map(#parse_raw)
+                    .transpose()?
+            }
+        }
+        FieldShape::Many => quote! {
+            matches
+                .filter_map(|found| #value)
+                .map(#parse_raw)
+                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+        },
+        FieldShape::ManyNested(_) | FieldShape::AttrMap => {
+            unreachable!("rejected while classifying the field")
+        }
+    }
+}
+
+/// Generates the gathering of a plain string-typed field.
+fn expand_plain(
+    shape: &FieldShape,
+    attrs: &FieldAttrs,
+    value: &TokenStream,
+    field: &str,
+    css: &str,
+) -> TokenStream {
+    match shape {
+        FieldShape::One => match &attrs.default {
+            Some(default) => quote! {
+                matches
+                    .filter_map(|found| #value)
+                    .next()
+                    .unwrap_or_else(|| #default.to_owned())
+            },
+            None => quote! {
+                matches
+                    .filter_map(|found| #value)
+                    .next()
+                    .ok_or_else(|| ::spire::extract::SelectError::missing(#field, #css))?
+            },
+        },
+        FieldShape::Maybe => match &attrs.default {
+            Some(default) => quote! {
+                matches
+                    .filter_map(|found| #value)
+                    .next()
+                    .or_else(|| ::std::option::Option::Some(#default.to_owned()))
+            },
+            None => quote! {
+                matches.filter_map(|found| #value).next()
+            },
+        },
+        FieldShape::Many => quote! {
+            matches
+                .filter_map(|found| #value)
+                .collect::<::std::vec::Vec<_>>()
+        },
+        FieldShape::ManyNested(inner) => quote! {
+            matches
+                .map(|found| {
+                    <#inner as ::spire::extract::Select>::select_with_base(&found, __base)
+                })
+                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+        },
+        FieldShape::AttrMap => quote! {
+            matches
+                .next()
+                .map(|found| ::spire::extract::element_attrs(&found))
+                .ok_or_else(|| ::spire::extract::SelectError::missing(#field, #css))?
+        },
+    }
+}
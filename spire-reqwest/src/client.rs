@@ -0,0 +1,242 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use spire_core::backend::{Backend, Client};
+use spire_core::context::{Body, Request, Response};
+use spire_core::{Error, ErrorKind, Result};
+
+/// HTTP backend and per-request client backed by a shared
+/// [`reqwest::Client`].
+///
+/// The same type serves as both [`Backend`] and [`Client`]: acquiring a
+/// client is a cheap clone, and all clones share one connection pool.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    inner: reqwest::Client,
+    stats: Arc<Mutex<HttpStats>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct HttpStats {
+    requests: u64,
+}
+
+impl HttpClient {
+    /// Creates a client with the default configuration.
+    pub fn new() -> Self {
+        Self::builder().build().expect("default client should build")
+    }
+
+    /// Starts building a customized client.
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::default()
+    }
+
+    /// Wraps a pre-built [`reqwest::Client`].
+    ///
+    /// Use this when the client needs settings the builder does not
+    /// expose, such as a cookie store or proxy configuration.
+    pub fn from_client(inner: reqwest::Client) -> Self {
+        Self {
+            inner,
+            stats: Arc::default(),
+        }
+    }
+
+    /// Returns the underlying [`reqwest::Client`].
+    ///
+    /// The returned client shares the connection pool — and a cookie
+    /// jar, if one is configured — with the backend, so ad-hoc side
+    /// requests reuse warm TLS sessions and session cookies. Requests
+    /// made through it are not counted by
+    /// [`HttpClient::request_count`], and anything affecting the shared
+    /// state (say, a login request updating cookies) is visible to the
+    /// crawler as well.
+    pub fn inner(&self) -> reqwest::Client {
+        self.inner.clone()
+    }
+
+    /// Returns the number of requests resolved so far.
+    pub fn request_count(&self) -> u64 {
+        self.stats().requests
+    }
+
+    /// Locks the shared stats, recovering the guard if a panic poisoned
+    /// the lock — the counter inside is always consistent.
+    fn stats(&self) -> std::sync::MutexGuard<'_, HttpStats> {
+        self.stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Client for HttpClient {
+    async fn resolve(&mut self, request: Request) -> Result<Response> {
+        let (parts, body) = request.into_parts();
+        let outgoing = self
+            .inner
+            .request(parts.method, parts.uri.to_string())
+            .headers(parts.headers)
+            .body(body.into_bytes())
+            .send()
+            .await
+            .map_err(|error| Error::new(ErrorKind::Http, error))?;
+
+        self.stats().requests += 1;
+
+        let mut response = http::Response::builder()
+            .status(outgoing.status())
+            .version(outgoing.version());
+        if let Some(headers) = response.headers_mut() {
+            headers.extend(outgoing.headers().clone());
+        }
+
+        let bytes = outgoing
+            .bytes()
+            .await
+            .map_err(|error| Error::new(ErrorKind::Http, error))?;
+        response
+            .body(Body::new(bytes))
+            .map_err(|error| Error::new(ErrorKind::Http, error))
+    }
+}
+
+#[async_trait]
+impl Backend for HttpClient {
+    type Client = HttpClient;
+
+    async fn client(&self) -> Result<Self::Client> {
+        Ok(self.clone())
+    }
+}
+
+/// Builder for an [`HttpClient`].
+#[derive(Debug, Default)]
+pub struct HttpClientBuilder {
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    #[cfg(feature = "http2")]
+    http2_prior_knowledge: bool,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    root_certificates: Vec<Vec<u8>>,
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    danger_accept_invalid_certs: bool,
+}
+
+impl HttpClientBuilder {
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the total per-request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Speaks HTTP/2 from the first byte, skipping the upgrade dance.
+    ///
+    /// Multiplexing many in-flight requests over one connection is a
+    /// sizeable win when hammering a single host, but the server must be
+    /// known to speak HTTP/2 — there is no fallback.
+    #[cfg(feature = "http2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http2")))]
+    pub fn with_http2_prior_knowledge(mut self, enable: bool) -> Self {
+        self.http2_prior_knowledge = enable;
+        self
+    }
+
+    /// Caps the idle connections kept alive per host.
+    ///
+    /// Defaults to reqwest's own default.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets how long an idle connection stays pooled before closing.
+    ///
+    /// Defaults to reqwest's own default.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Trusts an additional PEM-encoded root certificate.
+    ///
+    /// Needed for crawling behind a private CA, where the default trust
+    /// store rejects otherwise valid internal endpoints. May be called
+    /// repeatedly to stack several roots; the PEM is validated in
+    /// [`HttpClientBuilder::build`].
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "rustls-tls", feature = "native-tls"))))]
+    pub fn with_root_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Disables TLS certificate verification entirely.
+    ///
+    /// **Insecure**: the client will accept any certificate for any host,
+    /// exposing the crawl to man-in-the-middle interception. Prefer
+    /// [`HttpClientBuilder::with_root_certificate`] with the actual CA;
+    /// reserve this for throwaway setups where the endpoint's certificate
+    /// cannot be fixed or trusted properly.
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "rustls-tls", feature = "native-tls"))))]
+    pub fn with_danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// To start from an existing [`reqwest::Client`] instead, see
+    /// [`HttpClient::from_client`].
+    pub fn build(self) -> Result<HttpClient> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        #[cfg(feature = "http2")]
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+        {
+            for pem in self.root_certificates {
+                let certificate = reqwest::Certificate::from_pem(&pem)
+                    .map_err(|error| Error::new(ErrorKind::Backend, error))?;
+                builder = builder.add_root_certificate(certificate);
+            }
+            if self.danger_accept_invalid_certs {
+                builder = builder.danger_accept_invalid_certs(true);
+            }
+        }
+
+        let inner = builder
+            .build()
+            .map_err(|error| Error::new(ErrorKind::Backend, error))?;
+        Ok(HttpClient::from_client(inner))
+    }
+}
@@ -0,0 +1,20 @@
+//! Reqwest-powered HTTP backend for the [`spire`] crawler framework.
+//!
+//! [`HttpClient`] implements both the [`Backend`] and [`Client`] traits of
+//! `spire-core` on top of a shared [`reqwest::Client`], so cloning it is
+//! cheap and every clone reuses the same connection pool.
+//!
+//! The politeness middleware (`robots.txt` exclusion, sitemap seeding)
+//! lives in `spire_core::layer` and is backend-agnostic; it works with
+//! this backend without any extra wiring.
+//!
+//! [`spire`]: https://docs.rs/spire
+//! [`Backend`]: spire_core::backend::Backend
+//! [`Client`]: spire_core::backend::Client
+
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod client;
+
+pub use client::{HttpClient, HttpClientBuilder};
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use spire_core::backend::Backend;
+use spire_core::Result;
+
+use crate::client::BrowserClient;
+use crate::config::{ClientConfig, PoolConfig, WebDriverConfig};
+use crate::error::BrowserResult;
+use crate::pool::{AcquireStrategy, BrowserManager, BrowserPool, OnCreateHook};
+
+/// Browser backend handing out [`BrowserClient`]s from a session pool.
+#[derive(Debug, Clone)]
+pub struct BrowserBackend {
+    pool: Arc<BrowserPool>,
+    client_config: ClientConfig,
+}
+
+impl BrowserBackend {
+    /// Starts building a backend for the given WebDriver endpoint.
+    pub fn builder(webdriver: WebDriverConfig) -> BrowserBuilder {
+        BrowserBuilder::new(webdriver)
+    }
+
+    /// Returns the underlying session pool.
+    pub fn pool(&self) -> &BrowserPool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl Backend for BrowserBackend {
+    type Client = BrowserClient;
+
+    async fn client(&self) -> Result<Self::Client> {
+        let connection = self.pool.get().await.map_err(spire_core::Error::from)?;
+        let client = BrowserClient::new(connection)
+            .with_config(self.client_config.clone())
+            .with_navigation_permits(self.pool.navigation_permits())
+            .with_pool(self.pool.clone());
+        Ok(client)
+    }
+}
+
+/// Builder assembling a [`BrowserBackend`] from its configurations.
+pub struct BrowserBuilder {
+    webdriver: WebDriverConfig,
+    pool: PoolConfig,
+    client: ClientConfig,
+    acquire: Option<AcquireStrategy>,
+    max_navigations: Option<usize>,
+    circuit_breaker: Option<(usize, std::time::Duration)>,
+    on_create: Option<OnCreateHook>,
+}
+
+impl std::fmt::Debug for BrowserBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BrowserBuilder")
+            .field("webdriver", &self.webdriver)
+            .field("pool", &self.pool)
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BrowserBuilder {
+    /// Starts a builder for the given WebDriver endpoint.
+    pub fn new(webdriver: WebDriverConfig) -> Self {
+        Self {
+            webdriver,
+            pool: PoolConfig::default(),
+            client: ClientConfig::default(),
+            acquire: None,
+            max_navigations: None,
+            circuit_breaker: None,
+            on_create: None,
+        }
+    }
+
+    /// Replaces the pool configuration.
+    pub fn with_pool_config(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Replaces the per-client configuration.
+    pub fn with_client_config(mut self, client: ClientConfig) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Chooses how acquisition behaves while every session is busy.
+    ///
+    /// Defaults to [`AcquireStrategy::Wait`] with the pool configuration's
+    /// acquire timeout.
+    pub fn with_acquire_strategy(mut self, acquire: AcquireStrategy) -> Self {
+        self.acquire = Some(acquire);
+        self
+    }
+
+    /// Caps how many sessions may run `goto` at the same time.
+    ///
+    /// Each session in the pool navigates and extracts serially, so the
+    /// pool size alone controls parallelism. Page loads are the expensive
+    /// part for the WebDriver server, though: with a large pool, a burst
+    /// of simultaneous navigations can overload a shared server even when
+    /// extraction would be fine. This cap throttles navigation
+    /// independently of pool capacity; sessions over the cap wait for a
+    /// permit before navigating. Values below `1` are treated as `1`.
+    pub fn with_max_concurrent_navigations(mut self, limit: usize) -> Self {
+        self.max_navigations = Some(limit);
+        self
+    }
+
+    /// Fails acquisitions fast while the WebDriver endpoint looks dead.
+    ///
+    /// See [`BrowserPool::with_circuit_breaker`] for the state machine.
+    ///
+    /// [`BrowserPool::with_circuit_breaker`]: crate::BrowserPool::with_circuit_breaker
+    pub fn with_circuit_breaker(mut self, failures: usize, cooldown: std::time::Duration) -> Self {
+        self.circuit_breaker = Some((failures, cooldown));
+        self
+    }
+
+    /// Runs the given hook on every newly created browser session.
+    ///
+    /// See [`BrowserManager::with_on_create`] for the semantics and an
+    /// example.
+    pub fn with_on_create<F>(mut self, hook: F) -> Self
+    where
+        F: for<'a> Fn(
+                &'a thirtyfour::WebDriver,
+            )
+                -> std::pin::Pin<Box<dyn std::future::Future<Output = BrowserResult<()>> + Send + 'a>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.on_create = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builds the backend and its session pool.
+    ///
+    /// Sessions are established lazily on first acquisition, unless a
+    /// non-zero [`PoolConfig::with_min_size`] triggers a background
+    /// warm-up.
+    pub fn build(self) -> BrowserResult<BrowserBackend> {
+        let mut manager = BrowserManager::new(self.webdriver).with_recycling(&self.pool);
+        if let Some(hook) = self.on_create {
+            manager = manager.with_on_create_hook(hook);
+        }
+        let mut pool = BrowserPool::new(manager, &self.pool, self.acquire)?;
+        if let Some(limit) = self.max_navigations {
+            pool = pool.with_max_concurrent_navigations(limit);
+        }
+        if let Some((failures, cooldown)) = self.circuit_breaker {
+            pool = pool.with_circuit_breaker(failures, cooldown);
+        }
+
+        Ok(BrowserBackend {
+            pool: Arc::new(pool),
+            client_config: self.client,
+        })
+    }
+}
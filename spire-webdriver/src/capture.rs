@@ -0,0 +1,115 @@
+//! WebDriver BiDi capture of main-document response metadata.
+//!
+//! The WebDriver protocol itself never exposes the status code or headers
+//! of the navigated document, which is why [`BrowserClient`] otherwise
+//! fabricates a `200 OK`. With the `bidi` feature the client subscribes to
+//! [`network.responseCompleted`] events over the BiDi websocket and copies
+//! the real status, headers and mime type of the main document onto the
+//! [`Response`].
+//!
+//! [`BrowserClient`]: crate::BrowserClient
+//! [`network.responseCompleted`]: https://w3c.github.io/webdriver-bidi/#event-network-responseCompleted
+//! [`Response`]: spire_core::context::Response
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::Value;
+use thirtyfour::bidi::events::ResponseCompleted;
+use thirtyfour::bidi::modules::network::ResponseData;
+use thirtyfour::bidi::EventStream;
+use thirtyfour::WebDriver;
+
+use spire_core::context::Response;
+
+/// How long to wait for the main-document event after the page loaded.
+///
+/// The event normally fires well before `document.readyState` settles, so
+/// this only covers scheduling slack.
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// An active subscription to `network.responseCompleted` events.
+pub(crate) struct NetworkCapture {
+    stream: EventStream<ResponseCompleted>,
+}
+
+impl NetworkCapture {
+    /// Subscribes before navigation so the main-document event cannot be
+    /// missed. Returns `None` (and logs) when the session has no BiDi
+    /// connection, e.g. because `webSocketUrl` was not negotiated.
+    pub(crate) async fn begin(driver: &WebDriver) -> Option<Self> {
+        let bidi = match driver.bidi().await {
+            Ok(bidi) => bidi,
+            Err(error) => {
+                tracing::debug!(%error, "BiDi connection unavailable; response metadata not captured");
+                return None;
+            }
+        };
+
+        match bidi.subscribe::<ResponseCompleted>().await {
+            Ok(stream) => Some(Self { stream }),
+            Err(error) => {
+                tracing::debug!(%error, "failed to subscribe to network events");
+                None
+            }
+        }
+    }
+
+    /// Waits briefly for the event matching the main document and applies
+    /// its status, headers and mime type to the response.
+    pub(crate) async fn apply(mut self, url: &str, response: &mut Response) {
+        let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            let event = match tokio::time::timeout(remaining, self.stream.next()).await {
+                Ok(Some(event)) => event,
+                Ok(None) | Err(_) => {
+                    tracing::debug!(%url, "no network event observed for the main document");
+                    return;
+                }
+            };
+
+            // Redirects report the original request URL with the final
+            // response URL, so match on either side.
+            if event.request.url == url || event.response.url == url {
+                apply_metadata(&event.response, response);
+                return;
+            }
+        }
+    }
+}
+
+/// Copies the captured metadata onto the prepared response.
+fn apply_metadata(data: &ResponseData, response: &mut Response) {
+    if let Ok(status) = http::StatusCode::from_u16(data.status) {
+        *response.status_mut() = status;
+    }
+
+    for header in &data.headers {
+        let (Some(name), Some(value)) = (
+            header.get("name").and_then(Value::as_str),
+            header.pointer("/value/value").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+
+        let Ok(name) = http::HeaderName::try_from(name) else {
+            continue;
+        };
+        let Ok(value) = http::HeaderValue::try_from(value) else {
+            continue;
+        };
+        // The body is the rendered document, not the raw transfer, so the
+        // transfer-encoding headers of the original exchange do not apply.
+        if name == http::header::CONTENT_LENGTH || name == http::header::TRANSFER_ENCODING {
+            continue;
+        }
+        response.headers_mut().insert(name, value);
+    }
+
+    if let Some(mime) = data.extra.get("mimeType").and_then(Value::as_str) {
+        if let Ok(value) = http::HeaderValue::try_from(mime) {
+            response.headers_mut().insert(http::header::CONTENT_TYPE, value);
+        }
+    }
+}
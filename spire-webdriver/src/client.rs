@@ -0,0 +1,371 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool::managed::Object;
+use thirtyfour::By;
+use tokio::sync::Semaphore;
+
+use spire_core::backend::Client;
+use spire_core::context::{Body, PageContent, PageText, PageTitle, Request, Response};
+use spire_core::Result;
+
+use crate::config::{ClientConfig, WaitStrategy};
+use crate::error::{BrowserError, BrowserResult, NavigationErrorType};
+use crate::pool::{BrowserManager, BrowserPool, PinnedSession, SessionAffinity};
+use crate::view::View;
+
+/// Interval between page-load polls.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Script returning whether the document finished loading.
+const READY_STATE_SCRIPT: &str = "return document.readyState === 'complete';";
+
+/// Script returning the size of the resource-timing buffer.
+const RESOURCE_COUNT_SCRIPT: &str =
+    "return window.performance.getEntriesByType('resource').length;";
+
+/// Script returning the visible text of the page.
+const EXTRACT_TEXT_SCRIPT: &str = "return document.body ? document.body.innerText : '';";
+
+/// Request extension skipping the page-load wait for one request.
+///
+/// Some navigations never "complete" — streaming pages, long-poll
+/// endpoints — and the configured [`WaitStrategy`] would always time out
+/// on them. Inserted into the extensions of an outgoing request, this
+/// marker makes the [`BrowserClient`] extract content right after `goto`
+/// returns, regardless of the global `wait_for_load` setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SkipLoadWait;
+
+/// Marker for clients that drive a live browser session.
+///
+/// The driver extractors — [`View`] here and `ViewElements` in `spire` —
+/// bound their client on this trait, so routing such a handler onto a
+/// non-browser client (for example the plain HTTP backend) fails at
+/// compile time instead of during a crawl. Wrappers around
+/// [`BrowserClient`] opt in by delegating to its session.
+pub trait BrowserClientMarker {
+    /// Returns a [`View`] over the live browser session.
+    fn view(&self) -> View;
+}
+
+/// Per-request client resolving requests through a pooled browser session.
+///
+/// The session is checked out of the [`BrowserPool`] for the lifetime of
+/// this client and returns to the pool on drop.
+///
+/// [`BrowserPool`]: crate::BrowserPool
+pub struct BrowserClient {
+    connection: Object<BrowserManager>,
+    config: ClientConfig,
+    navigation_permits: Option<Arc<Semaphore>>,
+    pool: Option<Arc<BrowserPool>>,
+    pinned: Option<PinnedSession>,
+}
+
+impl BrowserClient {
+    /// Wraps a pooled session with the default [`ClientConfig`].
+    pub fn new(connection: Object<BrowserManager>) -> Self {
+        Self {
+            connection,
+            config: ClientConfig::default(),
+            navigation_permits: None,
+            pool: None,
+            pinned: None,
+        }
+    }
+
+    /// Replaces the configuration of this client.
+    pub fn with_config(mut self, config: ClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub(crate) fn with_navigation_permits(mut self, permits: Option<Arc<Semaphore>>) -> Self {
+        self.navigation_permits = permits;
+        self
+    }
+
+    pub(crate) fn with_pool(mut self, pool: Arc<BrowserPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Returns the session this client currently operates on: the pinned
+    /// one while a [`SessionAffinity`] request is being served, the
+    /// checked-out pool session otherwise.
+    fn session(&self) -> &Object<BrowserManager> {
+        match &self.pinned {
+            Some(pinned) => pinned.as_ref().expect("pinned slot is filled before use"),
+            None => &self.connection,
+        }
+    }
+
+    /// Returns a [`View`] over the live browser session.
+    pub fn view(&self) -> View {
+        View::new(self.session().driver().clone())
+    }
+
+    /// Navigates the session to the given URL.
+    ///
+    /// Holds a navigation permit for the duration of the `goto` when the
+    /// pool caps concurrent navigations.
+    async fn navigate_to_url(&self, url: &str) -> BrowserResult<()> {
+        let _permit = match &self.navigation_permits {
+            Some(permits) => permits.acquire().await.ok(),
+            None => None,
+        };
+
+        let driver = self.session().driver();
+        if let Err(error) = driver.goto(url).await {
+            // The WebDriver protocol reports navigation failures as opaque
+            // messages; classify the common ones by their text.
+            let message = error.to_string();
+            let kind = if message.contains("timeout") {
+                NavigationErrorType::Timeout
+            } else if message.contains("ERR_NAME_NOT_RESOLVED") {
+                NavigationErrorType::DnsError
+            } else if message.contains("ERR_CONNECTION_REFUSED") {
+                NavigationErrorType::ConnectionRefused
+            } else {
+                // HTTP errors are not guessed from the message: a 4xx/5xx
+                // main document still renders, so `goto` succeeds and the
+                // real status is classified after the network capture.
+                NavigationErrorType::Unknown
+            };
+
+            return Err(BrowserError::Navigation {
+                url: url.to_owned(),
+                kind,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the page to finish loading per the configured
+    /// [`WaitStrategy`], polling until `navigation_timeout` expires.
+    async fn wait_for_page_load(&self) -> BrowserResult<()> {
+        if !self.config.enable_javascript
+            && !matches!(self.config.wait_strategy, WaitStrategy::Selector(_))
+        {
+            // The ready-state and network-idle polls are injected scripts;
+            // `goto` already blocked until the driver's own page-load
+            // strategy was satisfied, so settle for that.
+            tracing::debug!("javascript disabled; skipping the script-based load wait");
+            return Ok(());
+        }
+
+        let timeout = self.config.navigation_timeout;
+        let deadline = Instant::now() + timeout;
+
+        if self.config.wait_strategy == WaitStrategy::NetworkIdle {
+            // Best effort: non-Chromium drivers reject CDP commands, and
+            // resource timing alone still detects settled network activity.
+            let cdp = self.session().driver().cdp();
+            if let Err(error) = cdp.network().enable().await {
+                tracing::debug!(%error, "failed to enable the CDP network domain");
+            }
+        }
+
+        let mut last_resource_count = None;
+        while Instant::now() < deadline {
+            if self.poll_page_loaded(&mut last_resource_count).await? {
+                return Ok(());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        Err(BrowserError::Timeout(format!(
+            "waiting for page load ({:?} via {:?})",
+            timeout, self.config.wait_strategy,
+        )))
+    }
+
+    /// Runs one poll of the configured wait strategy.
+    async fn poll_page_loaded(&self, last_resource_count: &mut Option<u64>) -> BrowserResult<bool> {
+        let driver = self.session().driver();
+        let script = |script: &'static str| async move {
+            driver
+                .execute(script, Vec::new())
+                .await
+                .map_err(|error| BrowserError::Script(error.to_string()))
+        };
+
+        match &self.config.wait_strategy {
+            WaitStrategy::ReadyState => {
+                let ready = script(READY_STATE_SCRIPT).await?;
+                Ok(ready.convert::<bool>().unwrap_or(false))
+            }
+            WaitStrategy::NetworkIdle => {
+                let ready = script(READY_STATE_SCRIPT).await?;
+                if !ready.convert::<bool>().unwrap_or(false) {
+                    return Ok(false);
+                }
+
+                // Idle means the resource-timing buffer did not grow since
+                // the previous poll.
+                let count = script(RESOURCE_COUNT_SCRIPT).await?;
+                let count = count.convert::<u64>().unwrap_or(0);
+                Ok(last_resource_count.replace(count) == Some(count))
+            }
+            WaitStrategy::Selector(css) => Ok(driver.find(By::Css(css)).await.is_ok()),
+        }
+    }
+
+    /// Extracts the rendered page content in a single pass.
+    async fn extract_content(&self) -> BrowserResult<PageContent> {
+        let driver = self.session().driver();
+
+        let html = match self.config.extract_html {
+            true => Some(driver.source().await?),
+            false => None,
+        };
+        let text = match self.config.extract_text {
+            // Get Element Text goes through the WebDriver protocol rather
+            // than an injected script, so it works with JavaScript off.
+            true if !self.config.enable_javascript => {
+                let body = driver.find(By::Tag("body")).await?;
+                Some(body.text().await?)
+            }
+            true => {
+                let ret = driver
+                    .execute(EXTRACT_TEXT_SCRIPT, Vec::new())
+                    .await
+                    .map_err(|error| BrowserError::Script(error.to_string()))?;
+                Some(ret.convert::<String>().unwrap_or_default())
+            }
+            false => None,
+        };
+
+        Ok(PageContent {
+            html,
+            text,
+            title: driver.title().await?,
+            url: driver.current_url().await?.to_string(),
+        })
+    }
+
+    /// Builds the response from the extracted content.
+    ///
+    /// The HTML becomes the body when extracted; the text then travels as
+    /// a [`PageText`] extension instead of being discarded. The title is
+    /// always attached as a [`PageTitle`] extension, and the whole
+    /// [`PageContent`] as its own extension for handlers wanting all of
+    /// it from the one extraction pass.
+    fn prepare_response(&self, content: PageContent) -> Response {
+        let page = content.clone();
+        let title = PageTitle(content.title);
+        let (body, content_type, text) = match (content.html, content.text) {
+            (Some(html), text) => (Body::new(html), "text/html; charset=utf-8", text),
+            (None, Some(text)) => (Body::new(text), "text/plain; charset=utf-8", None),
+            (None, None) => {
+                tracing::warn!(url = %content.url, "nothing extracted from page");
+                (Body::empty(), "text/plain; charset=utf-8", None)
+            }
+        };
+
+        let mut response = http::Response::builder()
+            .status(http::StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, content_type)
+            .body(body)
+            .expect("static response parts should build");
+        response.extensions_mut().insert(title);
+        response.extensions_mut().insert(page);
+        if let Some(text) = text {
+            response.extensions_mut().insert(PageText(text));
+        }
+
+        response
+    }
+}
+
+impl BrowserClientMarker for BrowserClient {
+    fn view(&self) -> View {
+        BrowserClient::view(self)
+    }
+}
+
+#[async_trait]
+impl Client for BrowserClient {
+    /// A browser session performs a full navigation regardless of the
+    /// request method, so a `HEAD` preflight saves nothing here.
+    fn supports_head(&self) -> bool {
+        false
+    }
+
+    async fn resolve(&mut self, request: Request) -> Result<Response> {
+        let url = request.uri().to_string();
+
+        let affinity = request.extensions().get::<SessionAffinity>().cloned();
+        if let Some(affinity) = &affinity {
+            match &self.pool {
+                Some(pool) => {
+                    let pinned = pool
+                        .get_pinned(affinity.key())
+                        .await
+                        .map_err(spire_core::Error::from)?;
+                    self.pinned = Some(pinned);
+                }
+                None => tracing::warn!(
+                    key = affinity.key(),
+                    "no pool attached; resolving on the checked-out session"
+                ),
+            }
+        }
+
+        self.session().record_request();
+
+        // Subscribe before navigating so the main-document event cannot
+        // slip past; without BiDi the response keeps its fabricated `200`.
+        #[cfg(feature = "bidi")]
+        let capture = crate::capture::NetworkCapture::begin(self.session().driver()).await;
+
+        let skip_wait = request.extensions().get::<SkipLoadWait>().is_some();
+        self.navigate_to_url(&url).await.map_err(spire_core::Error::from)?;
+        if self.config.wait_for_load && !skip_wait {
+            self.wait_for_page_load().await.map_err(spire_core::Error::from)?;
+        } else if skip_wait {
+            tracing::debug!(%url, "skipping the page-load wait for this request");
+        }
+
+        let content = self.extract_content().await.map_err(spire_core::Error::from)?;
+        #[cfg_attr(not(feature = "bidi"), allow(unused_mut))]
+        let mut response = self.prepare_response(content);
+
+        #[cfg(feature = "bidi")]
+        if let Some(capture) = capture {
+            capture.apply(&url, &mut response).await;
+
+            let status = response.status();
+            if self.config.fail_on_http_error
+                && (status.is_client_error() || status.is_server_error())
+            {
+                return Err(spire_core::Error::from(BrowserError::Navigation {
+                    url,
+                    kind: NavigationErrorType::HttpError(status.as_u16()),
+                }));
+            }
+        }
+
+        if let Some(affinity) = affinity.filter(SessionAffinity::releases) {
+            if let Some(pool) = &self.pool {
+                pool.release_session(affinity.key());
+            }
+            // Dropping the guard sends the session back to the pool now
+            // that the map no longer holds it.
+            self.pinned = None;
+        }
+
+        Ok(response)
+    }
+}
+
+impl std::fmt::Debug for BrowserClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BrowserClient")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,617 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::pool::RecycleMethod;
+
+/// Merges two capability layers; `overrides` wins on conflicting keys.
+///
+/// Layering is shallow: a conflicting key is replaced wholesale, not
+/// deep-merged. Chain calls to stack more than two layers, keeping in mind
+/// that later layers win. The result is ordered, so the serialized
+/// capabilities are stable across runs.
+pub fn capabilities_layered(
+    base: impl IntoIterator<Item = (String, Value)>,
+    overrides: impl IntoIterator<Item = (String, Value)>,
+) -> BTreeMap<String, Value> {
+    let mut layered: BTreeMap<String, Value> = base.into_iter().collect();
+    layered.extend(overrides);
+    layered
+}
+
+/// The browser family a WebDriver session is requested for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Browser {
+    /// Google Chrome / Chromium via chromedriver.
+    #[default]
+    Chrome,
+    /// Mozilla Firefox via geckodriver.
+    Firefox,
+    /// Microsoft Edge via msedgedriver.
+    Edge,
+}
+
+impl Browser {
+    /// Returns the W3C `browserName` capability value.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Browser::Chrome => "chrome",
+            Browser::Firefox => "firefox",
+            Browser::Edge => "MicrosoftEdge",
+        }
+    }
+
+    /// Returns the vendor-specific options capability key.
+    fn options_key(&self) -> &'static str {
+        match self {
+            Browser::Chrome => "goog:chromeOptions",
+            Browser::Firefox => "moz:firefoxOptions",
+            Browser::Edge => "ms:edgeOptions",
+        }
+    }
+}
+
+/// Validation failure of an environment-derived configuration; see
+/// [`WebDriverConfig::from_env`] and [`PoolConfig::from_env`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigEnvError {
+    /// A variable was set to a value that does not parse or validate.
+    #[error("`{0}` has invalid value `{1}`")]
+    Invalid(&'static str, String),
+}
+
+/// Reads a whole-second timeout from the environment, rejecting zero.
+fn env_duration_secs(var: &'static str, default: Duration) -> Result<Duration, ConfigEnvError> {
+    let Ok(value) = std::env::var(var) else {
+        return Ok(default);
+    };
+
+    match value.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+        _ => Err(ConfigEnvError::Invalid(var, value)),
+    }
+}
+
+/// Reads a size from the environment.
+fn env_usize(var: &'static str, default: usize) -> Result<usize, ConfigEnvError> {
+    let Ok(value) = std::env::var(var) else {
+        return Ok(default);
+    };
+
+    value.parse().map_err(|_| ConfigEnvError::Invalid(var, value))
+}
+
+/// Connection settings for a WebDriver endpoint.
+#[derive(Debug, Clone)]
+pub struct WebDriverConfig {
+    pub(crate) endpoint: String,
+    pub(crate) browser: Browser,
+    pub(crate) capabilities: BTreeMap<String, Value>,
+    pub(crate) connect_timeout: Duration,
+}
+
+impl WebDriverConfig {
+    /// Creates a configuration for the given WebDriver endpoint, e.g.
+    /// `http://127.0.0.1:4444`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            browser: Browser::default(),
+            capabilities: BTreeMap::new(),
+            connect_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Creates a configuration from environment variables.
+    ///
+    /// Reads `SPIRE_WEBDRIVER_URL` (default `http://127.0.0.1:4444`),
+    /// `SPIRE_BROWSER` (`chrome`, `firefox` or `edge`),
+    /// `SPIRE_CONNECT_TIMEOUT` (whole seconds) and `SPIRE_HEADLESS`
+    /// (`true`/`false`). Unset variables fall back to the defaults of
+    /// [`WebDriverConfig::new`]; a set-but-invalid value is an error
+    /// rather than a silent fallback.
+    ///
+    /// Builder methods still apply afterwards, so deployment-specific
+    /// settings can come from the environment while capabilities stay in
+    /// code.
+    pub fn from_env() -> Result<Self, ConfigEnvError> {
+        let endpoint = std::env::var("SPIRE_WEBDRIVER_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:4444".to_owned());
+        let mut config = Self::new(endpoint);
+
+        if let Ok(value) = std::env::var("SPIRE_BROWSER") {
+            config.browser = match value.to_ascii_lowercase().as_str() {
+                "chrome" | "chromium" => Browser::Chrome,
+                "firefox" => Browser::Firefox,
+                "edge" => Browser::Edge,
+                _ => return Err(ConfigEnvError::Invalid("SPIRE_BROWSER", value)),
+            };
+        }
+
+        config.connect_timeout =
+            env_duration_secs("SPIRE_CONNECT_TIMEOUT", config.connect_timeout)?;
+
+        if let Ok(value) = std::env::var("SPIRE_HEADLESS") {
+            match value.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" => config = config.headless(),
+                "0" | "false" | "no" => {}
+                _ => return Err(ConfigEnvError::Invalid("SPIRE_HEADLESS", value)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Selects the browser family to request sessions for.
+    pub fn with_browser(mut self, browser: Browser) -> Self {
+        self.browser = browser;
+        self
+    }
+
+    /// Merges additional raw capabilities into the session request.
+    ///
+    /// Capabilities layer in call order: a key set here replaces the same
+    /// key from earlier calls or presets like [`WebDriverConfig::headless`].
+    /// See [`capabilities_layered`] for combining capability maps up front.
+    pub fn with_capabilities(
+        mut self,
+        capabilities: impl IntoIterator<Item = (String, Value)>,
+    ) -> Self {
+        self.capabilities = capabilities_layered(self.capabilities, capabilities);
+        self
+    }
+
+    /// Sets the timeout for establishing a session.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Makes the browser accept untrusted or invalid TLS certificates
+    /// via the W3C `acceptInsecureCerts` capability.
+    ///
+    /// **Insecure**: the session will render pages served with any
+    /// certificate, so only enable this against hosts you control. For
+    /// private-CA setups, prefer installing the CA into the browser
+    /// profile — the WebDriver protocol has no portable way to add a
+    /// trusted root per session.
+    pub fn with_danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.capabilities
+            .insert("acceptInsecureCerts".to_owned(), Value::from(accept));
+        self
+    }
+
+    /// Requests a headless browser via the vendor options capability.
+    pub fn headless(mut self) -> Self {
+        let args = match self.browser {
+            Browser::Firefox => json!({ "args": ["-headless"] }),
+            _ => json!({ "args": ["--headless=new", "--disable-gpu"] }),
+        };
+        self.capabilities
+            .insert(self.browser.options_key().to_owned(), args);
+        self
+    }
+
+    /// Requests a browser profile with JavaScript disabled.
+    ///
+    /// Merges into the vendor options capability, so it stacks with
+    /// [`WebDriverConfig::headless`]. Pair with
+    /// [`ClientConfigBuilder::enable_javascript`] set to `false` so the
+    /// client also stops injecting scripts of its own.
+    pub fn without_javascript(mut self) -> Self {
+        let key = self.browser.options_key().to_owned();
+        let mut options = match self.capabilities.remove(&key) {
+            Some(Value::Object(options)) => options,
+            _ => serde_json::Map::new(),
+        };
+
+        match self.browser {
+            Browser::Firefox => {
+                let prefs = options.entry("prefs").or_insert_with(|| json!({}));
+                if let Value::Object(prefs) = prefs {
+                    prefs.insert("javascript.enabled".to_owned(), Value::from(false));
+                }
+            }
+            _ => {
+                let args = options.entry("args").or_insert_with(|| json!([]));
+                if let Value::Array(args) = args {
+                    args.push(Value::from("--blink-settings=scriptEnabled=false"));
+                }
+            }
+        }
+
+        self.capabilities.insert(key, Value::Object(options));
+        self
+    }
+
+    /// Returns the configured WebDriver endpoint.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Assembles the effective session capabilities.
+    ///
+    /// With the `bidi` feature a BiDi websocket is negotiated via the
+    /// `webSocketUrl` capability, enabling network-event capture.
+    pub(crate) fn build_capabilities(&self) -> thirtyfour::Capabilities {
+        let mut capabilities = thirtyfour::Capabilities::new();
+        let _ = capabilities.set("browserName", self.browser.name());
+        #[cfg(feature = "bidi")]
+        let _ = capabilities.set("webSocketUrl", true);
+        for (key, value) in &self.capabilities {
+            let _ = capabilities.set(key.clone(), value.clone());
+        }
+
+        capabilities
+    }
+}
+
+/// How the client decides a navigated page has finished loading.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WaitStrategy {
+    /// Poll until `document.readyState` is `complete`.
+    #[default]
+    ReadyState,
+    /// Poll until the document is complete and the resource-timing buffer
+    /// stops growing, i.e. no new network activity between two polls.
+    ///
+    /// On Chromium browsers the Network devtools domain is enabled over
+    /// CDP first; other browsers rely on resource timing alone.
+    NetworkIdle,
+    /// Poll until an element matches the given CSS selector.
+    ///
+    /// The most reliable option for single-page apps rendering content
+    /// well after `readyState` settles.
+    Selector(String),
+}
+
+/// Per-client behavior of the browser backend.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub(crate) wait_for_load: bool,
+    pub(crate) wait_strategy: WaitStrategy,
+    pub(crate) navigation_timeout: Duration,
+    pub(crate) script_timeout: Duration,
+    pub(crate) extract_html: bool,
+    pub(crate) extract_text: bool,
+    pub(crate) enable_javascript: bool,
+    pub(crate) fail_on_http_error: bool,
+}
+
+impl ClientConfig {
+    /// Starts building a customized configuration.
+    pub fn builder() -> ClientConfigBuilder {
+        ClientConfigBuilder::default()
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            wait_for_load: true,
+            wait_strategy: WaitStrategy::default(),
+            navigation_timeout: Duration::from_secs(30),
+            script_timeout: Duration::from_secs(10),
+            extract_html: true,
+            extract_text: false,
+            enable_javascript: true,
+            fail_on_http_error: false,
+        }
+    }
+}
+
+/// Validation failure of a [`ClientConfigBuilder`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum ClientConfigError {
+    /// A timeout was configured as zero.
+    #[error("`{0}` must be greater than zero")]
+    ZeroTimeout(&'static str),
+    /// Neither HTML nor text extraction is enabled.
+    #[error("at least one of `extract_html` and `extract_text` must be enabled")]
+    NothingToExtract,
+}
+
+/// Builder for a [`ClientConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfigBuilder {
+    config: ClientConfig,
+}
+
+impl ClientConfigBuilder {
+    /// Toggles waiting for the document to finish loading after navigation.
+    pub fn wait_for_load(mut self, wait: bool) -> Self {
+        self.config.wait_for_load = wait;
+        self
+    }
+
+    /// Selects how the client detects a finished page load.
+    ///
+    /// The wait polls until [`ClientConfigBuilder::navigation_timeout`]
+    /// expires.
+    pub fn wait_strategy(mut self, strategy: WaitStrategy) -> Self {
+        self.config.wait_strategy = strategy;
+        self
+    }
+
+    /// Sets the navigation timeout.
+    pub fn navigation_timeout(mut self, timeout: Duration) -> Self {
+        self.config.navigation_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for injected scripts.
+    pub fn script_timeout(mut self, timeout: Duration) -> Self {
+        self.config.script_timeout = timeout;
+        self
+    }
+
+    /// Toggles storing the rendered HTML as the response body.
+    pub fn extract_html(mut self, extract: bool) -> Self {
+        self.config.extract_html = extract;
+        self
+    }
+
+    /// Toggles extracting the visible page text.
+    pub fn extract_text(mut self, extract: bool) -> Self {
+        self.config.extract_text = extract;
+        self
+    }
+
+    /// Toggles JavaScript-dependent processing.
+    ///
+    /// When disabled, the client skips the script-based load waits and
+    /// extracts the page text through the WebDriver protocol instead of
+    /// an injected script. Pair with
+    /// [`WebDriverConfig::without_javascript`] to also disable scripts
+    /// inside the browser.
+    pub fn enable_javascript(mut self, enable: bool) -> Self {
+        self.config.enable_javascript = enable;
+        self
+    }
+
+    /// Toggles failing requests whose main document came back 4xx/5xx.
+    ///
+    /// When enabled, such a navigation fails with
+    /// [`NavigationErrorType::HttpError`] carrying the actual status;
+    /// when disabled (the default), the rendered page is extracted like
+    /// any other response, with the status preserved for the handler.
+    ///
+    /// The real status is only visible with the `bidi` feature; without
+    /// it the client fabricates `200 OK` and this toggle has no effect.
+    ///
+    /// [`NavigationErrorType::HttpError`]: crate::NavigationErrorType::HttpError
+    pub fn fail_on_http_error(mut self, fail: bool) -> Self {
+        self.config.fail_on_http_error = fail;
+        self
+    }
+
+    /// Validates and returns the configuration.
+    pub fn build(self) -> Result<ClientConfig, ClientConfigError> {
+        if self.config.navigation_timeout.is_zero() {
+            return Err(ClientConfigError::ZeroTimeout("navigation_timeout"));
+        }
+        if self.config.script_timeout.is_zero() {
+            return Err(ClientConfigError::ZeroTimeout("script_timeout"));
+        }
+        if !self.config.extract_html && !self.config.extract_text {
+            return Err(ClientConfigError::NothingToExtract);
+        }
+
+        Ok(self.config)
+    }
+}
+
+/// Sizing, acquisition and recycling settings of the [`BrowserPool`].
+///
+/// [`BrowserPool`]: crate::BrowserPool
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub(crate) max_size: usize,
+    pub(crate) min_size: usize,
+    pub(crate) acquire_timeout: Duration,
+    pub(crate) recycle: RecycleMethod,
+    /// Maximum total lifetime of a pooled session.
+    pub max_lifetime: Duration,
+    /// Maximum idle time of a pooled session.
+    pub max_idle_time: Duration,
+}
+
+impl PoolConfig {
+    /// Creates a pool configuration with the given maximum size.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size: max_size.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a pool configuration from environment variables.
+    ///
+    /// Reads `SPIRE_POOL_MAX_SIZE`, `SPIRE_POOL_MIN_SIZE` and
+    /// `SPIRE_POOL_ACQUIRE_TIMEOUT` (whole seconds). Unset variables fall
+    /// back to the [`PoolConfig::default`] values; a set-but-invalid
+    /// value is an error rather than a silent fallback.
+    pub fn from_env() -> Result<Self, ConfigEnvError> {
+        let defaults = Self::default();
+        Ok(Self {
+            max_size: env_usize("SPIRE_POOL_MAX_SIZE", defaults.max_size)?.max(1),
+            min_size: env_usize("SPIRE_POOL_MIN_SIZE", defaults.min_size)?,
+            acquire_timeout: env_duration_secs(
+                "SPIRE_POOL_ACQUIRE_TIMEOUT",
+                defaults.acquire_timeout,
+            )?,
+            ..defaults
+        })
+    }
+
+    /// Sets the number of sessions the pool aims to keep available.
+    ///
+    /// A non-zero minimum triggers a background warm-up at pool
+    /// construction; see [`BrowserPool::wait_ready`].
+    ///
+    /// [`BrowserPool::wait_ready`]: crate::BrowserPool::wait_ready
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the timeout for acquiring a session from the pool.
+    pub fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
+    /// Chooses how returned sessions are checked before reuse.
+    ///
+    /// Defaults to [`RecycleMethod::Verified`].
+    pub fn with_recycle_check(mut self, recycle: RecycleMethod) -> Self {
+        self.recycle = recycle;
+        self
+    }
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 4,
+            min_size: 0,
+            acquire_timeout: Duration::from_secs(30),
+            recycle: RecycleMethod::default(),
+            max_lifetime: Duration::from_secs(30 * 60),
+            max_idle_time: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn headless_sets_vendor_options() {
+        let config = WebDriverConfig::new("http://127.0.0.1:4444").headless();
+        let capabilities = config.build_capabilities();
+        assert!(capabilities.contains_key("goog:chromeOptions"));
+        assert_eq!(
+            capabilities.get("browserName"),
+            Some(&Value::from("chrome"))
+        );
+    }
+
+    #[test]
+    fn later_capability_layers_win() {
+        let base = BTreeMap::from([("custom:key".to_owned(), Value::from("base"))]);
+        let overrides = [("custom:key".to_owned(), Value::from("override"))];
+        let layered = capabilities_layered(base, overrides);
+        assert_eq!(layered.get("custom:key"), Some(&Value::from("override")));
+
+        let config = WebDriverConfig::new("http://127.0.0.1:4444")
+            .headless()
+            .with_capabilities([("goog:chromeOptions".to_owned(), json!({ "args": [] }))]);
+        let capabilities = config.build_capabilities();
+        assert_eq!(
+            capabilities.get("goog:chromeOptions"),
+            Some(&json!({ "args": [] }))
+        );
+    }
+
+    #[test]
+    fn javascript_toggle_merges_vendor_options() {
+        let config = WebDriverConfig::new("http://127.0.0.1:4444")
+            .headless()
+            .without_javascript();
+        let capabilities = config.build_capabilities();
+        let options = capabilities.get("goog:chromeOptions").unwrap();
+        let args = options.get("args").and_then(Value::as_array).unwrap();
+        assert!(args.contains(&Value::from("--headless=new")));
+        assert!(args.contains(&Value::from("--blink-settings=scriptEnabled=false")));
+
+        let config = WebDriverConfig::new("http://127.0.0.1:4444")
+            .with_browser(Browser::Firefox)
+            .without_javascript();
+        let capabilities = config.build_capabilities();
+        let options = capabilities.get("moz:firefoxOptions").unwrap();
+        assert_eq!(
+            options.get("prefs").and_then(|prefs| prefs.get("javascript.enabled")),
+            Some(&Value::from(false)),
+        );
+    }
+
+    #[test]
+    fn insecure_certs_set_the_w3c_capability() {
+        let config = WebDriverConfig::new("http://127.0.0.1:4444")
+            .with_danger_accept_invalid_certs(true);
+        let capabilities = config.build_capabilities();
+        assert_eq!(
+            capabilities.get("acceptInsecureCerts"),
+            Some(&Value::from(true))
+        );
+    }
+
+    #[test]
+    fn env_configuration_overrides_defaults() {
+        // One test covers every variable: the names are process-global,
+        // so splitting this up would race under the parallel test runner.
+        std::env::set_var("SPIRE_WEBDRIVER_URL", "http://grid:4444");
+        std::env::set_var("SPIRE_BROWSER", "firefox");
+        std::env::set_var("SPIRE_CONNECT_TIMEOUT", "5");
+        std::env::set_var("SPIRE_HEADLESS", "true");
+        std::env::set_var("SPIRE_POOL_MAX_SIZE", "8");
+        std::env::set_var("SPIRE_POOL_ACQUIRE_TIMEOUT", "15");
+
+        let config = WebDriverConfig::from_env().unwrap();
+        assert_eq!(config.endpoint(), "http://grid:4444");
+        assert_eq!(config.browser, Browser::Firefox);
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
+        assert!(config.capabilities.contains_key("moz:firefoxOptions"));
+
+        let pool = PoolConfig::from_env().unwrap();
+        assert_eq!(pool.max_size, 8);
+        assert_eq!(pool.min_size, PoolConfig::default().min_size);
+        assert_eq!(pool.acquire_timeout, Duration::from_secs(15));
+
+        std::env::set_var("SPIRE_BROWSER", "netscape");
+        let error = WebDriverConfig::from_env().unwrap_err();
+        assert!(matches!(error, ConfigEnvError::Invalid("SPIRE_BROWSER", _)));
+
+        std::env::set_var("SPIRE_BROWSER", "chrome");
+        std::env::set_var("SPIRE_CONNECT_TIMEOUT", "0");
+        let error = WebDriverConfig::from_env().unwrap_err();
+        assert!(matches!(error, ConfigEnvError::Invalid("SPIRE_CONNECT_TIMEOUT", _)));
+
+        for var in [
+            "SPIRE_WEBDRIVER_URL",
+            "SPIRE_BROWSER",
+            "SPIRE_CONNECT_TIMEOUT",
+            "SPIRE_HEADLESS",
+            "SPIRE_POOL_MAX_SIZE",
+            "SPIRE_POOL_ACQUIRE_TIMEOUT",
+        ] {
+            std::env::remove_var(var);
+        }
+    }
+
+    #[test]
+    fn builder_rejects_zero_timeouts() {
+        let error = ClientConfig::builder()
+            .navigation_timeout(Duration::ZERO)
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, ClientConfigError::ZeroTimeout(_)));
+    }
+
+    #[test]
+    fn builder_requires_extraction() {
+        let error = ClientConfig::builder()
+            .extract_html(false)
+            .extract_text(false)
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, ClientConfigError::NothingToExtract));
+    }
+}
@@ -0,0 +1,159 @@
+use spire_core::{Error, ErrorKind};
+
+use crate::config::ClientConfigError;
+
+/// Specialized result alias for browser operations.
+pub type BrowserResult<T> = Result<T, BrowserError>;
+
+/// Classification of a failed navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NavigationErrorType {
+    /// The navigation did not finish within the configured timeout.
+    Timeout,
+    /// The main document answered with the given HTTP status.
+    HttpError(u16),
+    /// The hostname could not be resolved.
+    DnsError,
+    /// The connection to the host was refused.
+    ConnectionRefused,
+    /// Any other navigation failure.
+    Unknown,
+}
+
+/// The error type of the browser backend.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum BrowserError {
+    /// A raw WebDriver protocol failure.
+    #[error("webdriver error: {0}")]
+    WebDriver(#[from] thirtyfour::error::WebDriverError),
+
+    /// Navigating to a URL failed.
+    #[error("navigation to {url} failed: {kind:?}")]
+    Navigation {
+        /// The URL that failed to load.
+        url: String,
+        /// Classification of the failure.
+        kind: NavigationErrorType,
+    },
+
+    /// No browser session could be acquired from the pool.
+    #[error("browser pool exhausted")]
+    PoolExhausted,
+
+    /// An element lookup failed.
+    #[error("element not found: {0}")]
+    Element(String),
+
+    /// An injected script failed to execute.
+    #[error("script error: {0}")]
+    Script(String),
+
+    /// An operation did not finish in time.
+    #[error("timed out: {0}")]
+    Timeout(String),
+
+    /// An invalid client configuration was rejected at build time.
+    #[error("configuration error: {0}")]
+    Configuration(#[from] ClientConfigError),
+
+    /// A pooled session failed its health check during recycling.
+    #[error("unhealthy session at {endpoint}: {message}")]
+    Unhealthy {
+        /// The WebDriver endpoint the session is bound to.
+        endpoint: String,
+        /// The failure reported by the health check.
+        message: String,
+    },
+
+    /// The pool's circuit breaker is open after repeated failures.
+    ///
+    /// See [`BrowserPool::with_circuit_breaker`].
+    ///
+    /// [`BrowserPool::with_circuit_breaker`]: crate::BrowserPool::with_circuit_breaker
+    #[error("backend unavailable: {0}")]
+    Unavailable(String),
+}
+
+impl BrowserError {
+    /// Returns a coarse category name for metrics and logging.
+    pub fn category(&self) -> &'static str {
+        match self {
+            BrowserError::WebDriver(_) => "webdriver",
+            BrowserError::Navigation { .. } => "navigation",
+            BrowserError::PoolExhausted => "pool",
+            BrowserError::Element(_) => "element",
+            BrowserError::Script(_) => "script",
+            BrowserError::Timeout(_) => "timeout",
+            BrowserError::Configuration(_) => "configuration",
+            BrowserError::Unhealthy { .. } => "session",
+            BrowserError::Unavailable(_) => "unavailable",
+        }
+    }
+}
+
+impl From<BrowserError> for Error {
+    fn from(error: BrowserError) -> Self {
+        // Preserve the classification so callers matching on the core
+        // [`ErrorKind`] can tell failure modes apart without parsing the
+        // rendered message.
+        let kind = match &error {
+            BrowserError::Timeout(_) => ErrorKind::Timeout,
+            BrowserError::Navigation {
+                kind: NavigationErrorType::Timeout,
+                ..
+            } => ErrorKind::Timeout,
+            BrowserError::PoolExhausted => ErrorKind::Pool,
+            BrowserError::Unavailable(_) => ErrorKind::Pool,
+            BrowserError::Element(_) => ErrorKind::Element,
+            _ => ErrorKind::Backend,
+        };
+
+        Error::new(kind, error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn categories_are_stable() {
+        assert_eq!(BrowserError::PoolExhausted.category(), "pool");
+        let navigation = BrowserError::Navigation {
+            url: "https://example.com/".to_owned(),
+            kind: NavigationErrorType::Timeout,
+        };
+        assert_eq!(navigation.category(), "navigation");
+    }
+
+    #[test]
+    fn config_errors_convert() {
+        let error = crate::ClientConfig::builder()
+            .extract_html(false)
+            .extract_text(false)
+            .build()
+            .unwrap_err();
+        let error = BrowserError::from(error);
+        assert_eq!(error.category(), "configuration");
+        assert_eq!(Error::from(error).kind(), ErrorKind::Backend);
+    }
+
+    #[test]
+    fn conversion_preserves_classification() {
+        assert_eq!(Error::from(BrowserError::PoolExhausted).kind(), ErrorKind::Pool);
+        assert_eq!(
+            Error::from(BrowserError::Timeout("page load".to_owned())).kind(),
+            ErrorKind::Timeout,
+        );
+        assert_eq!(
+            Error::from(BrowserError::Element(".missing".to_owned())).kind(),
+            ErrorKind::Element,
+        );
+        assert_eq!(
+            Error::from(BrowserError::Script("oops".to_owned())).kind(),
+            ErrorKind::Backend,
+        );
+    }
+}
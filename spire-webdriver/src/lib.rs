@@ -0,0 +1,47 @@
+//! WebDriver (browser) backend for the [`spire`] crawler framework.
+//!
+//! [`BrowserBackend`] drives real browser sessions through a WebDriver
+//! endpoint (chromedriver, geckodriver or a Selenium grid) using
+//! [`thirtyfour`], pooling sessions with [`deadpool`]. Each request checks
+//! a [`BrowserConnection`] out of the [`BrowserPool`], navigates to the
+//! target URL and renders the page before handing the response to the
+//! routing machinery.
+//!
+//! Exactly one TLS implementation must be enabled: either the
+//! `rustls-tls` (default) or the `native-tls` feature.
+//!
+//! [`spire`]: https://docs.rs/spire
+
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
+compile_error!("either the `rustls-tls` or the `native-tls` feature must be enabled");
+
+#[cfg(all(feature = "rustls-tls", feature = "native-tls"))]
+compile_error!(
+    "the `rustls-tls` and `native-tls` features are mutually exclusive; \
+     disable default features to use `native-tls`"
+);
+
+mod backend;
+#[cfg(feature = "bidi")]
+mod capture;
+mod client;
+mod config;
+mod error;
+mod pool;
+mod view;
+
+pub use backend::{BrowserBackend, BrowserBuilder};
+pub use client::{BrowserClient, BrowserClientMarker, SkipLoadWait};
+pub use config::{
+    capabilities_layered, Browser, ClientConfig, ClientConfigBuilder, ClientConfigError,
+    ConfigEnvError, PoolConfig, WaitStrategy, WebDriverConfig,
+};
+pub use error::{BrowserError, BrowserResult, NavigationErrorType};
+pub use pool::{
+    AcquireStrategy, BrowserConnection, BrowserManager, BrowserPool, ConnectionStats,
+    OnCreateHook, RecycleMethod, SessionAffinity,
+};
+pub use view::View;
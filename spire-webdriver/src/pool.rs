@@ -0,0 +1,719 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use deadpool::managed::{Manager, Metrics, Object, Pool, PoolError, RecycleResult, Timeouts};
+use thirtyfour::WebDriver;
+use tokio::sync::{watch, Mutex as AsyncMutex, OwnedMutexGuard, Semaphore};
+
+use crate::config::{PoolConfig, WebDriverConfig};
+use crate::error::{BrowserError, BrowserResult};
+
+/// A pooled WebDriver session.
+#[derive(Debug)]
+pub struct BrowserConnection {
+    driver: WebDriver,
+    endpoint: String,
+    created_at: Instant,
+    stats: Arc<ConnectionStatsInner>,
+}
+
+#[derive(Debug, Default)]
+struct ConnectionStatsInner {
+    requests: AtomicU64,
+}
+
+/// Usage counters of a single [`BrowserConnection`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// The WebDriver endpoint the connection is bound to.
+    pub endpoint: String,
+    /// Number of requests resolved through this connection.
+    pub requests: u64,
+}
+
+impl BrowserConnection {
+    /// Returns the underlying WebDriver session handle.
+    pub fn driver(&self) -> &WebDriver {
+        &self.driver
+    }
+
+    /// Returns how long ago this session was created.
+    pub fn age(&self) -> std::time::Duration {
+        self.created_at.elapsed()
+    }
+
+    /// Returns the WebDriver endpoint this session was established against.
+    ///
+    /// Useful for correlating failures to a specific node of a grid.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns a snapshot of the usage counters.
+    pub fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            endpoint: self.endpoint.clone(),
+            requests: self.stats.requests.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.stats.requests.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// How a session returned to the pool is checked before reuse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecycleMethod {
+    /// Only enforce the lifetime and idle limits; skip the liveness probe.
+    ///
+    /// Saves one WebDriver round trip per checkout at the risk of handing
+    /// out a session that died while idle.
+    Fast,
+    /// Additionally probe the session with a WebDriver command.
+    #[default]
+    Verified,
+}
+
+/// Hook run against every newly created browser session.
+///
+/// See [`BrowserManager::with_on_create`].
+pub type OnCreateHook = Arc<
+    dyn for<'a> Fn(&'a WebDriver) -> Pin<Box<dyn Future<Output = BrowserResult<()>> + Send + 'a>>
+        + Send
+        + Sync,
+>;
+
+/// Creates and recycles [`BrowserConnection`]s for the [`BrowserPool`].
+pub struct BrowserManager {
+    config: WebDriverConfig,
+    max_lifetime: Option<Duration>,
+    max_idle_time: Option<Duration>,
+    recycle_method: RecycleMethod,
+    on_create: Option<OnCreateHook>,
+}
+
+impl BrowserManager {
+    /// Creates a manager establishing sessions per the given configuration.
+    pub fn new(config: WebDriverConfig) -> Self {
+        Self {
+            config,
+            max_lifetime: None,
+            max_idle_time: None,
+            recycle_method: RecycleMethod::default(),
+            on_create: None,
+        }
+    }
+
+    /// Runs the given hook on every newly created session, before it
+    /// enters the pool.
+    ///
+    /// The place for fixed per-session setup — window size, driver
+    /// timeouts, a stealth shim. A failing hook fails the session
+    /// creation: the half-initialized session is closed and the error
+    /// propagates to whoever requested it.
+    ///
+    /// ```no_run
+    /// # use spire_webdriver::{BrowserManager, WebDriverConfig};
+    /// let manager = BrowserManager::new(WebDriverConfig::new("http://localhost:4444"))
+    ///     .with_on_create(|driver| {
+    ///         Box::pin(async move {
+    ///             driver.maximize_window().await?;
+    ///             Ok(())
+    ///         })
+    ///     });
+    /// ```
+    pub fn with_on_create<F>(mut self, hook: F) -> Self
+    where
+        F: for<'a> Fn(&'a WebDriver) -> Pin<Box<dyn Future<Output = BrowserResult<()>> + Send + 'a>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.on_create = Some(Arc::new(hook));
+        self
+    }
+
+    pub(crate) fn with_on_create_hook(mut self, hook: OnCreateHook) -> Self {
+        self.on_create = Some(hook);
+        self
+    }
+
+    /// Enforces the recycling policy of the given pool configuration:
+    /// lifetime and idle limits plus the configured [`RecycleMethod`].
+    pub fn with_recycling(mut self, config: &PoolConfig) -> Self {
+        self.max_lifetime = Some(config.max_lifetime);
+        self.max_idle_time = Some(config.max_idle_time);
+        self.recycle_method = config.recycle;
+        self
+    }
+
+    /// Returns why a session must not be reused, if any limit is exceeded.
+    fn expired_reason(&self, age: Duration, idle: Duration) -> Option<&'static str> {
+        if self.max_lifetime.is_some_and(|limit| age > limit) {
+            return Some("exceeded max_lifetime");
+        }
+        if self.max_idle_time.is_some_and(|limit| idle > limit) {
+            return Some("exceeded max_idle_time");
+        }
+
+        None
+    }
+}
+
+impl std::fmt::Debug for BrowserManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BrowserManager")
+            .field("config", &self.config)
+            .field("recycle_method", &self.recycle_method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Manager for BrowserManager {
+    type Type = BrowserConnection;
+    type Error = BrowserError;
+
+    async fn create(&self) -> Result<BrowserConnection, BrowserError> {
+        let capabilities = self.config.build_capabilities();
+        let endpoint = self.config.endpoint.clone();
+        let driver = tokio::time::timeout(
+            self.config.connect_timeout,
+            WebDriver::new(endpoint.clone(), capabilities),
+        )
+        .await
+        .map_err(|_| BrowserError::Timeout(format!("connecting to {endpoint}")))??;
+
+        if let Some(hook) = &self.on_create {
+            if let Err(error) = hook(&driver).await {
+                tracing::warn!(%endpoint, %error, "session setup hook failed");
+                // Close the half-initialized session instead of leaking it.
+                let _ = driver.quit().await;
+                return Err(error);
+            }
+        }
+
+        tracing::debug!(%endpoint, "created browser session");
+        Ok(BrowserConnection {
+            driver,
+            endpoint,
+            created_at: Instant::now(),
+            stats: Arc::default(),
+        })
+    }
+
+    async fn recycle(
+        &self,
+        connection: &mut BrowserConnection,
+        metrics: &Metrics,
+    ) -> RecycleResult<BrowserError> {
+        // Aged-out sessions are dropped and recreated instead of reused.
+        if let Some(reason) = self.expired_reason(connection.age(), metrics.last_used()) {
+            let endpoint = connection.endpoint();
+            tracing::debug!(%endpoint, %reason, "dropping browser session");
+            return Err(BrowserError::Unhealthy {
+                endpoint: endpoint.to_owned(),
+                message: reason.to_owned(),
+            }
+            .into());
+        }
+
+        if self.recycle_method == RecycleMethod::Fast {
+            return Ok(());
+        }
+
+        // Verify the session still answers before handing it out again.
+        connection.driver.title().await.map_err(|error| {
+            let endpoint = connection.endpoint();
+            tracing::debug!(%endpoint, %error, "browser session failed the recycle check");
+            BrowserError::Unhealthy {
+                endpoint: endpoint.to_owned(),
+                message: error.to_string(),
+            }
+        })?;
+
+        tracing::trace!(endpoint = connection.endpoint(), "recycled browser session");
+        Ok(())
+    }
+}
+
+/// Request extension pinning a request to a named browser session.
+///
+/// Inserted into the extensions of an outgoing request (e.g. via
+/// [`Context::get_mut`] in middleware, or by enqueueing a prepared
+/// request through `RequestQueue::append_request`), it makes the
+/// [`BrowserClient`] resolve the request on the session registered under
+/// the key instead of an arbitrary pooled one. Same-key requests
+/// serialize on that session, so a login request and the scrapes that
+/// depend on its cookies can share one browser.
+///
+/// A pinned session stays out of the general pool — and skips the
+/// recycling checks — until it is released, either by resolving a
+/// request whose affinity was built with [`SessionAffinity::release`] or
+/// explicitly via [`BrowserPool::release_session`]. Forgetting to
+/// release keeps one pool slot occupied for the rest of the crawl.
+///
+/// [`Context::get_mut`]: spire_core::context::Context::get_mut
+/// [`BrowserClient`]: crate::BrowserClient
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionAffinity {
+    key: Arc<str>,
+    release: bool,
+}
+
+impl SessionAffinity {
+    /// Pins the request to the session registered under `key`, keeping
+    /// the session pinned afterwards.
+    pub fn new(key: impl AsRef<str>) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            release: false,
+        }
+    }
+
+    /// Pins the request to the session registered under `key` and
+    /// releases the session back to the pool once the request resolves.
+    ///
+    /// The marker for the last request of a multi-step flow.
+    pub fn release(key: impl AsRef<str>) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            release: true,
+        }
+    }
+
+    /// Returns the affinity key.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns `true` if the session is released after this request.
+    pub fn releases(&self) -> bool {
+        self.release
+    }
+}
+
+/// A session held for a [`SessionAffinity`] key; `None` until the first
+/// same-key request checks one out of the pool.
+type PinnedSlot = Arc<AsyncMutex<Option<Object<BrowserManager>>>>;
+
+/// Exclusive use of a pinned session for the duration of one request.
+pub(crate) type PinnedSession = OwnedMutexGuard<Option<Object<BrowserManager>>>;
+
+/// State machine guarding the pool against a dead WebDriver endpoint.
+///
+/// See [`BrowserPool::with_circuit_breaker`].
+#[derive(Debug)]
+struct CircuitBreaker {
+    threshold: usize,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    /// Acquisitions flow normally; `failures` counts the consecutive ones.
+    Closed { failures: usize },
+    /// Every acquisition fails fast until the cooldown elapses.
+    Open { since: Instant },
+    /// One probe acquisition is in flight; its outcome decides the state.
+    HalfOpen,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: usize, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Decides whether an acquisition may proceed right now.
+    fn admit(&self) -> BrowserResult<()> {
+        let mut state = self.state.lock().expect("breaker lock poisoned");
+        match *state {
+            BreakerState::Closed { .. } => Ok(()),
+            BreakerState::Open { since } => {
+                let elapsed = since.elapsed();
+                if elapsed < self.cooldown {
+                    let remaining = self.cooldown - elapsed;
+                    return Err(BrowserError::Unavailable(format!(
+                        "circuit breaker open for another {remaining:?}"
+                    )));
+                }
+                // Cooldown over: let exactly one caller probe the endpoint.
+                *state = BreakerState::HalfOpen;
+                Ok(())
+            }
+            BreakerState::HalfOpen => Err(BrowserError::Unavailable(
+                "circuit breaker half-open, probe in flight".to_owned(),
+            )),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().expect("breaker lock poisoned");
+        *state = BreakerState::Closed { failures: 0 };
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().expect("breaker lock poisoned");
+        *state = match *state {
+            BreakerState::Closed { failures } if failures + 1 < self.threshold => {
+                BreakerState::Closed {
+                    failures: failures + 1,
+                }
+            }
+            _ => {
+                tracing::warn!(
+                    threshold = self.threshold,
+                    cooldown = ?self.cooldown,
+                    "circuit breaker opened",
+                );
+                BreakerState::Open {
+                    since: Instant::now(),
+                }
+            }
+        };
+    }
+}
+
+/// How [`BrowserPool::get`] behaves while all sessions are checked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcquireStrategy {
+    /// Fail immediately with [`BrowserError::PoolExhausted`].
+    FailFast,
+    /// Wait up to the given duration, then fail with the timeout surfaced
+    /// in the error message.
+    Wait(Duration),
+    /// Wait until a session is returned, however long that takes.
+    WaitForever,
+}
+
+/// Pool of WebDriver sessions shared by all [`BrowserClient`]s.
+///
+/// [`BrowserClient`]: crate::BrowserClient
+#[derive(Debug, Clone)]
+pub struct BrowserPool {
+    pool: Pool<BrowserManager>,
+    strategy: AcquireStrategy,
+    navigation_permits: Option<Arc<Semaphore>>,
+    warmup: Option<watch::Receiver<WarmupState>>,
+    pinned: Arc<Mutex<HashMap<String, PinnedSlot>>>,
+    breaker: Option<Arc<CircuitBreaker>>,
+}
+
+/// Progress of the background session warm-up.
+#[derive(Debug, Clone)]
+enum WarmupState {
+    Pending,
+    Ready,
+    Failed { endpoint: String, message: String },
+}
+
+impl BrowserPool {
+    /// Builds a pool on top of the given manager.
+    ///
+    /// The pool waits [`PoolConfig::acquire_timeout`] for a free session
+    /// unless [`BrowserBuilder::with_acquire_strategy`] chose otherwise.
+    ///
+    /// [`PoolConfig::acquire_timeout`]: crate::PoolConfig::with_acquire_timeout
+    /// [`BrowserBuilder::with_acquire_strategy`]: crate::BrowserBuilder::with_acquire_strategy
+    pub fn new(
+        manager: BrowserManager,
+        config: &PoolConfig,
+        strategy: Option<AcquireStrategy>,
+    ) -> BrowserResult<Self> {
+        let endpoint = manager.config.endpoint.clone();
+        let pool = Pool::builder(manager)
+            .max_size(config.max_size)
+            .build()
+            .map_err(|_| BrowserError::PoolExhausted)?;
+        let strategy = strategy.unwrap_or(AcquireStrategy::Wait(config.acquire_timeout));
+        let warmup = (config.min_size > 0).then(|| Self::spawn_warmup(&pool, config, endpoint));
+        Ok(Self {
+            pool,
+            strategy,
+            navigation_permits: None,
+            warmup,
+            pinned: Arc::default(),
+            breaker: None,
+        })
+    }
+
+    /// Starts establishing [`PoolConfig::min_size`] sessions in the
+    /// background, so early requests do not pay session-creation latency.
+    ///
+    /// [`PoolConfig::min_size`]: crate::PoolConfig::with_min_size
+    fn spawn_warmup(
+        pool: &Pool<BrowserManager>,
+        config: &PoolConfig,
+        endpoint: String,
+    ) -> watch::Receiver<WarmupState> {
+        let (sender, receiver) = watch::channel(WarmupState::Pending);
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::debug!("no tokio runtime; skipping pool warm-up");
+            let _ = sender.send(WarmupState::Ready);
+            return receiver;
+        };
+
+        let pool = pool.clone();
+        let count = config.min_size.min(config.max_size);
+        let timeouts = Timeouts {
+            wait: Some(config.acquire_timeout),
+            create: Some(config.acquire_timeout),
+            ..Timeouts::default()
+        };
+
+        handle.spawn(async move {
+            // Hold every session until the last one exists, otherwise the
+            // pool would hand the same one back repeatedly.
+            let mut held = Vec::with_capacity(count);
+            for _ in 0..count {
+                match pool.timeout_get(&timeouts).await {
+                    Ok(connection) => held.push(connection),
+                    Err(error) => {
+                        tracing::warn!(%endpoint, %error, "pool warm-up failed");
+                        let _ = sender.send(WarmupState::Failed {
+                            endpoint,
+                            message: error.to_string(),
+                        });
+                        return;
+                    }
+                }
+            }
+
+            tracing::debug!(%endpoint, sessions = count, "pool warmed up");
+            drop(held);
+            let _ = sender.send(WarmupState::Ready);
+        });
+
+        receiver
+    }
+
+    /// Waits for the background warm-up to finish.
+    ///
+    /// Returns immediately when the pool has no minimum size. A failed
+    /// warm-up surfaces here, making this the place to validate that the
+    /// WebDriver endpoint is reachable before the crawl starts; the pool
+    /// itself still works after a failed warm-up, creating sessions
+    /// lazily as before.
+    pub async fn wait_ready(&self) -> BrowserResult<()> {
+        let Some(receiver) = &self.warmup else {
+            return Ok(());
+        };
+
+        let mut receiver = receiver.clone();
+        loop {
+            let state = receiver.borrow_and_update().clone();
+            match state {
+                WarmupState::Ready => return Ok(()),
+                WarmupState::Failed { endpoint, message } => {
+                    return Err(BrowserError::Unhealthy { endpoint, message });
+                }
+                WarmupState::Pending => {
+                    if receiver.changed().await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Caps how many sessions may navigate at the same time, independent
+    /// of the pool size.
+    ///
+    /// Pool capacity decides how many pages can be *held* concurrently;
+    /// this cap decides how many may *load* at once, protecting a shared
+    /// WebDriver server from navigation bursts. See
+    /// [`BrowserBuilder::with_max_concurrent_navigations`].
+    ///
+    /// [`BrowserBuilder::with_max_concurrent_navigations`]: crate::BrowserBuilder::with_max_concurrent_navigations
+    pub fn with_max_concurrent_navigations(mut self, limit: usize) -> Self {
+        self.navigation_permits = Some(Arc::new(Semaphore::new(limit.max(1))));
+        self
+    }
+
+    pub(crate) fn navigation_permits(&self) -> Option<Arc<Semaphore>> {
+        self.navigation_permits.clone()
+    }
+
+    /// Fails acquisitions fast while the WebDriver endpoint looks dead.
+    ///
+    /// After `failures` consecutive failed acquisitions the breaker
+    /// opens: for the next `cooldown` every [`BrowserPool::get`] returns
+    /// [`BrowserError::Unavailable`] immediately instead of hammering the
+    /// endpoint with doomed connection attempts. Once the cooldown
+    /// elapses, a single acquisition is let through as a probe; its
+    /// success closes the breaker, its failure reopens it for another
+    /// cooldown. Values of `failures` below `1` are treated as `1`.
+    pub fn with_circuit_breaker(mut self, failures: usize, cooldown: Duration) -> Self {
+        self.breaker = Some(Arc::new(CircuitBreaker::new(failures, cooldown)));
+        self
+    }
+
+    /// Acquires a session per the configured [`AcquireStrategy`].
+    pub async fn get(&self) -> BrowserResult<Object<BrowserManager>> {
+        if let Some(breaker) = &self.breaker {
+            breaker.admit()?;
+        }
+
+        let result = match self.strategy {
+            AcquireStrategy::FailFast => {
+                let timeouts = Timeouts {
+                    wait: Some(Duration::ZERO),
+                    ..Timeouts::default()
+                };
+                self.pool.timeout_get(&timeouts).await
+            }
+            AcquireStrategy::Wait(timeout) => {
+                let timeouts = Timeouts {
+                    wait: Some(timeout),
+                    ..Timeouts::default()
+                };
+                self.pool.timeout_get(&timeouts).await
+            }
+            AcquireStrategy::WaitForever => self.pool.get().await,
+        };
+
+        let result = result.map_err(|error| match (error, self.strategy) {
+            (PoolError::Backend(error), _) => error,
+            (PoolError::Timeout(_), AcquireStrategy::Wait(timeout)) => {
+                BrowserError::Timeout(format!("acquiring a session (waited {timeout:?})"))
+            }
+            _ => BrowserError::PoolExhausted,
+        });
+
+        if let Some(breaker) = &self.breaker {
+            match &result {
+                Ok(_) => breaker.record_success(),
+                Err(_) => breaker.record_failure(),
+            }
+        }
+
+        result
+    }
+
+    /// Acquires exclusive use of the session pinned under `key`,
+    /// checking one out of the pool on the first use of the key.
+    ///
+    /// Same-key callers queue on the session; see [`SessionAffinity`].
+    pub(crate) async fn get_pinned(&self, key: &str) -> BrowserResult<PinnedSession> {
+        let slot = {
+            let mut pinned = self.pinned.lock().expect("pinned map lock poisoned");
+            pinned.entry(key.to_owned()).or_default().clone()
+        };
+
+        // The slot is filled outside the map lock, so creating a session
+        // for one key never stalls lookups or releases of the others.
+        let mut session = slot.lock_owned().await;
+        if session.is_none() {
+            *session = Some(self.get().await?);
+        }
+
+        Ok(session)
+    }
+
+    /// Releases the session pinned under `key` back to the pool.
+    ///
+    /// Returns `false` when no session was pinned under the key. A
+    /// request currently using the session finishes undisturbed; the
+    /// session returns to the pool right after.
+    pub fn release_session(&self, key: &str) -> bool {
+        let slot = {
+            let mut pinned = self.pinned.lock().expect("pinned map lock poisoned");
+            pinned.remove(key)
+        };
+
+        slot.is_some()
+    }
+
+    /// Returns the number of sessions currently checked in or out.
+    pub fn size(&self) -> usize {
+        self.pool.status().size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn affinity_marks_release() {
+        let pinned = SessionAffinity::new("login-flow");
+        assert_eq!(pinned.key(), "login-flow");
+        assert!(!pinned.releases());
+
+        let last = SessionAffinity::release("login-flow");
+        assert!(last.releases());
+        assert_eq!(pinned.key(), last.key());
+    }
+
+    #[test]
+    fn breaker_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.admit().is_ok());
+
+        // A success in between resets the consecutive count.
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.admit().is_ok());
+
+        breaker.record_failure();
+        let error = breaker.admit().unwrap_err();
+        assert!(matches!(error, BrowserError::Unavailable(_)));
+    }
+
+    #[test]
+    fn breaker_half_open_admits_a_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        breaker.record_failure();
+
+        // The cooldown elapsed instantly: one probe is admitted, and
+        // anyone else fails fast until its outcome is recorded.
+        assert!(breaker.admit().is_ok());
+        assert!(breaker.admit().is_err());
+
+        breaker.record_success();
+        assert!(breaker.admit().is_ok());
+    }
+
+    #[test]
+    fn failed_probe_reopens_the_breaker() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(breaker.admit().is_err());
+
+        // Simulate the cooldown elapsing, then a failing probe.
+        *breaker.state.lock().unwrap() = BreakerState::HalfOpen;
+        breaker.record_failure();
+        let error = breaker.admit().unwrap_err();
+        assert!(matches!(error, BrowserError::Unavailable(_)));
+    }
+
+    #[test]
+    fn aged_sessions_are_not_reused() {
+        let mut config = PoolConfig::new(4);
+        config.max_lifetime = Duration::from_secs(60);
+        config.max_idle_time = Duration::from_secs(10);
+        let manager =
+            BrowserManager::new(WebDriverConfig::new("http://localhost:4444")).with_recycling(&config);
+
+        let fresh = manager.expired_reason(Duration::from_secs(1), Duration::from_secs(1));
+        assert_eq!(fresh, None);
+
+        let old = manager.expired_reason(Duration::from_secs(90), Duration::from_secs(1));
+        assert_eq!(old, Some("exceeded max_lifetime"));
+
+        let idle = manager.expired_reason(Duration::from_secs(30), Duration::from_secs(30));
+        assert_eq!(idle, Some("exceeded max_idle_time"));
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use thirtyfour::{By, WebDriver};
+
+use spire_core::context::Context;
+use spire_core::extract::{FromContext, Rejection};
+
+use spire_core::backend::Client;
+
+use crate::client::BrowserClientMarker;
+use crate::error::{BrowserError, BrowserResult};
+
+/// A handle onto the live DOM of the current browser session.
+///
+/// Extractable in handlers running on the browser backend; element
+/// lookups run against the page the session last navigated to.
+#[derive(Debug, Clone)]
+pub struct View {
+    driver: WebDriver,
+}
+
+impl View {
+    pub(crate) fn new(driver: WebDriver) -> Self {
+        Self { driver }
+    }
+
+    /// Returns the underlying WebDriver session handle.
+    pub fn driver(&self) -> &WebDriver {
+        &self.driver
+    }
+
+    /// Returns the title of the current page.
+    pub async fn title(&self) -> BrowserResult<String> {
+        Ok(self.driver.title().await?)
+    }
+
+    /// Returns the serialized source of the current page.
+    pub async fn source(&self) -> BrowserResult<String> {
+        Ok(self.driver.source().await?)
+    }
+
+    /// Returns the URL the session currently points at.
+    pub async fn current_url(&self) -> BrowserResult<String> {
+        Ok(self.driver.current_url().await?.to_string())
+    }
+
+    /// Returns the text content of the first element matching the selector.
+    pub async fn find_text(&self, css: &str) -> BrowserResult<String> {
+        let element = self
+            .driver
+            .find(By::Css(css))
+            .await
+            .map_err(|_| BrowserError::Element(css.to_owned()))?;
+        Ok(element.text().await?)
+    }
+
+    /// Returns the text content of every element matching the selector.
+    pub async fn find_all_text(&self, css: &str) -> BrowserResult<Vec<String>> {
+        let elements = self.driver.find_all(By::Css(css)).await?;
+        let mut texts = Vec::with_capacity(elements.len());
+        for element in elements {
+            texts.push(element.text().await?);
+        }
+
+        Ok(texts)
+    }
+
+    /// Returns the serialized outer HTML of every element matching the
+    /// selector.
+    ///
+    /// Elements going stale between lookup and serialization are skipped
+    /// rather than failing the whole call.
+    pub async fn find_all_outer_html(&self, css: &str) -> BrowserResult<Vec<String>> {
+        let elements = self.driver.find_all(By::Css(css)).await?;
+        let mut snapshots = Vec::with_capacity(elements.len());
+        for element in elements {
+            match element.outer_html().await {
+                Ok(html) => snapshots.push(html),
+                Err(error) => {
+                    tracing::debug!(%css, %error, "element went stale before serialization");
+                }
+            }
+        }
+
+        Ok(snapshots)
+    }
+
+    /// Runs the given JavaScript in the page and deserializes its return
+    /// value into `T`.
+    ///
+    /// The typed escape hatch for extraction the selector-based helpers
+    /// cannot express, without reaching into thirtyfour directly. The
+    /// script receives `args` as its `arguments` array; its return value
+    /// is deserialized with serde, so `T` can be anything from a scalar
+    /// to a nested struct. A throwing script or a return value not
+    /// matching `T` surfaces as [`BrowserError::Script`].
+    pub async fn execute<T>(&self, script: &str, args: Vec<serde_json::Value>) -> BrowserResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let ret = self
+            .driver
+            .execute(script, args)
+            .await
+            .map_err(|error| BrowserError::Script(error.to_string()))?;
+
+        serde_json::from_value(ret.json().clone()).map_err(|error| {
+            BrowserError::Script(format!("script result does not match the expected type: {error}"))
+        })
+    }
+
+    /// Scrolls the page to its current bottom.
+    pub async fn scroll_to_bottom(&self) -> BrowserResult<()> {
+        self.execute("window.scrollTo(0, document.body.scrollHeight);", Vec::new())
+            .await
+    }
+
+    /// Repeatedly scrolls to the bottom until the page height stops
+    /// changing or `max_iters` scrolls were performed, pausing for
+    /// `pause` after each scroll so lazily loaded content can render.
+    ///
+    /// The standard loop for infinite-scroll listings: each scroll
+    /// triggers the next batch of content, and a stable height means the
+    /// page ran out. Returns the number of scrolls performed.
+    pub async fn scroll_until_stable(
+        &self,
+        max_iters: usize,
+        pause: Duration,
+    ) -> BrowserResult<usize> {
+        let mut height: u64 = self
+            .execute("return document.body.scrollHeight;", Vec::new())
+            .await?;
+
+        for iteration in 0..max_iters {
+            self.scroll_to_bottom().await?;
+            tokio::time::sleep(pause).await;
+
+            let current = self
+                .execute("return document.body.scrollHeight;", Vec::new())
+                .await?;
+            if current == height {
+                return Ok(iteration + 1);
+            }
+
+            height = current;
+        }
+
+        Ok(max_iters)
+    }
+
+    /// Returns an attribute of the first element matching the selector.
+    pub async fn find_attr(&self, css: &str, attr: &str) -> BrowserResult<Option<String>> {
+        let element = self
+            .driver
+            .find(By::Css(css))
+            .await
+            .map_err(|_| BrowserError::Element(css.to_owned()))?;
+        Ok(element.attr(attr).await?)
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for View
+where
+    C: Client + BrowserClientMarker,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        // Make sure the session has navigated before exposing the DOM.
+        cx.resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("View: {error}")))?;
+        Ok(cx.client().view())
+    }
+}
@@ -0,0 +1,49 @@
+//! Renders pages through a local chromedriver and scrapes the live DOM.
+//!
+//! Requires a WebDriver endpoint listening on `http://127.0.0.1:4444`:
+//!
+//! ```sh
+//! chromedriver --port=4444 &
+//! cargo run --example chrome --features webdriver
+//! ```
+
+use spire::backend::{BrowserBackend, View, WebDriverConfig};
+use spire::context::RequestQueue;
+use spire::dataset::{Data, InMemDataset};
+use spire::{Client, Result, Router};
+
+/// Scrapes the rendered listing page through the browser session.
+async fn listing(view: View, queue: RequestQueue, data: Data<String>) -> Result<()> {
+    let title = view.title().await?;
+    let url = view.current_url().await?;
+    println!("rendered {url} ({title})");
+
+    for quote in view.find_all_text(".quote > .text").await? {
+        data.write(quote).await?;
+    }
+
+    if let Ok(Some(href)) = view.find_attr(".next > a", "href").await {
+        queue.append(format!("https://quotes.toscrape.com{href}")).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let webdriver = WebDriverConfig::new("http://127.0.0.1:4444").headless();
+    let backend = BrowserBackend::builder(webdriver)
+        .build()
+        .expect("failed to build the browser backend");
+
+    let dataset = InMemDataset::<String>::queue();
+    let router = Router::new().route("listing", listing);
+    let client = Client::new(backend, router)
+        .with_dataset(dataset.clone())
+        .with_seed("listing", "https://quotes.toscrape.com/js/");
+    client.run().await?;
+
+    let quotes = Data::new(dataset).read_all().await?;
+    println!("collected {} quotes", quotes.len());
+    Ok(())
+}
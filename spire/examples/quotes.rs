@@ -0,0 +1,76 @@
+//! Crawls `quotes.toscrape.com`, following pagination and collecting
+//! every quote into an in-memory dataset.
+//!
+//! ```sh
+//! cargo run --example quotes
+//! ```
+
+use spire::backend::HttpClient;
+use spire::context::RequestQueue;
+use spire::dataset::{Data, InMemDataset};
+use spire::extract::{Elements, Html};
+use spire::{Client, Result, Router, Select};
+
+#[derive(Debug, Clone, Select)]
+#[select(css = ".quote")]
+struct Quote {
+    #[select(css = ".text")]
+    text: String,
+    #[select(css = ".author")]
+    author: String,
+    #[select(css = ".tag")]
+    tags: Vec<String>,
+}
+
+/// Collects the quotes of one listing page and follows the `Next` link.
+async fn quotes(
+    queue: RequestQueue,
+    quotes: Elements<Quote>,
+    html: Html,
+    data: Data<Quote>,
+) -> Result<()> {
+    for quote in quotes.0 {
+        data.write(quote).await?;
+    }
+
+    let next = {
+        let document = html.parse();
+        let selector = spire::__private::Selector::parse(".next > a").unwrap();
+        document
+            .select(&selector)
+            .next()
+            .and_then(|a| a.attr("href"))
+            .map(str::to_owned)
+    };
+
+    if let Some(href) = next {
+        let url = format!("https://quotes.toscrape.com{href}");
+        queue.append(url).await?;
+    }
+
+    Ok(())
+}
+
+/// Prints everything collected during the crawl.
+async fn display_results(data: &Data<Quote>) -> Result<()> {
+    let quotes = data.read_all().await?;
+    println!("collected {} quotes", quotes.len());
+    for quote in quotes {
+        println!("{} — {} {:?}", quote.text, quote.author, quote.tags);
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let dataset = InMemDataset::<Quote>::queue();
+    let router = Router::new().route("quotes", quotes);
+
+    let client = Client::new(HttpClient::new(), router)
+        .with_dataset(dataset.clone())
+        .with_seed("quotes", "https://quotes.toscrape.com/");
+    client.run().await?;
+
+    display_results(&Data::new(dataset)).await
+}
@@ -0,0 +1,49 @@
+//! POSTs form data to a search endpoint and processes the JSON result.
+//!
+//! Uses `httpbin.org`, which echoes the submitted form back as JSON.
+//!
+//! ```sh
+//! cargo run --example search
+//! ```
+
+use serde::Deserialize;
+use spire::backend::HttpClient;
+use spire::context::{RequestQueue, Task};
+use spire::extract::Json;
+use spire::http::Method;
+use spire::{Client, Result, Router};
+
+#[derive(Debug, Deserialize)]
+struct Echo {
+    form: std::collections::HashMap<String, String>,
+}
+
+/// Enqueues the search request itself: a POST with a form body.
+async fn start(queue: RequestQueue) -> Result<()> {
+    let mut search = Task::builder("https://httpbin.org/post")
+        .with_method(Method::POST)
+        .with_body("q=rust+crawler")
+        .with_tag("results")
+        .build()?;
+    search.request_mut().headers_mut().insert(
+        spire::http::header::CONTENT_TYPE,
+        "application/x-www-form-urlencoded".parse().expect("static header value"),
+    );
+
+    queue.append_request(search).await?;
+    Ok(())
+}
+
+/// Reads the echoed JSON response of the search.
+async fn results(Json(echo): Json<Echo>) {
+    println!("submitted form: {:?}", echo.form);
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let router = Router::new().route("start", start).route("results", results);
+    let client = Client::new(HttpClient::new(), router)
+        .with_seed("start", "https://httpbin.org/get");
+
+    client.run().await
+}
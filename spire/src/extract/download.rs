@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_core::Stream;
+use tokio::io::AsyncWriteExt;
+
+use spire_core::backend::Client;
+use spire_core::context::Context;
+use spire_core::extract::{FromContext, Rejection};
+use spire_core::{Error, ErrorKind};
+
+/// Size of the chunks yielded while consuming a download.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Extractor for byte-level progress over a large response body.
+///
+/// Yields the body in chunks via its [`Stream`] implementation, keeping
+/// a running count of consumed bytes next to the total the server
+/// advertised in `Content-Length` — exactly what a progress bar needs.
+/// The total is `None` when the server omits the header.
+///
+/// [`Download::to_file`] covers the common case of persisting the body
+/// while reporting progress:
+///
+/// ```ignore
+/// async fn handler(download: Download) -> Result<()> {
+///     download
+///         .to_file("asset.bin", |written, total| match total {
+///             Some(total) => println!("{written}/{total} bytes"),
+///             None => println!("{written} bytes"),
+///         })
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Note that response bodies are currently buffered by the backend, so
+/// the response is fully downloaded before the first chunk is yielded;
+/// the progress reported here is write progress, not network progress.
+#[derive(Debug)]
+pub struct Download {
+    bytes: Bytes,
+    total: Option<u64>,
+    offset: usize,
+}
+
+impl Download {
+    /// Returns the total size advertised via `Content-Length`, if any.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the number of bytes consumed from the stream so far.
+    pub fn consumed(&self) -> u64 {
+        self.offset as u64
+    }
+
+    /// Writes the body to the given path, invoking `progress` with the
+    /// running byte count and the advertised total after every chunk.
+    ///
+    /// Returns the number of bytes written.
+    pub async fn to_file(
+        mut self,
+        path: impl AsRef<Path>,
+        mut progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<u64, Error> {
+        let io = |error: std::io::Error| Error::new(ErrorKind::Context, error);
+        let mut file = tokio::fs::File::create(path).await.map_err(io)?;
+
+        let total = self.total;
+        while self.offset < self.bytes.len() {
+            let end = (self.offset + CHUNK_SIZE).min(self.bytes.len());
+            file.write_all(&self.bytes[self.offset..end]).await.map_err(io)?;
+            self.offset = end;
+            progress(self.offset as u64, total);
+        }
+
+        file.flush().await.map_err(io)?;
+        Ok(self.offset as u64)
+    }
+}
+
+impl Stream for Download {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.offset >= this.bytes.len() {
+            return Poll::Ready(None);
+        }
+
+        let end = (this.offset + CHUNK_SIZE).min(this.bytes.len());
+        let chunk = this.bytes.slice(this.offset..end);
+        this.offset = end;
+        Poll::Ready(Some(chunk))
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Download
+where
+    C: Client,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let total = cx
+            .resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("Download: {error}")))?
+            .headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Download: {error}")))?;
+
+        Ok(Download {
+            bytes: body.into_bytes(),
+            total,
+            offset: 0,
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+
+use spire_core::backend::Client;
+use spire_core::context::Context;
+use spire_core::extract::{FromContext, Rejection};
+use spire_core::{Error, ErrorKind};
+
+/// Extractor deserializing the response body as JSON.
+///
+/// Attempts JSON parsing regardless of the advertised content type and
+/// only rejects on parse errors: APIs mislabeling JSON as `text/plain`
+/// (or omitting the header entirely) are common enough that a
+/// content-type check rejects more real JSON than it catches garbage.
+/// Use [`StrictJson`] when an endpoint is trusted to label correctly and
+/// a mislabeled body should be treated as a bug rather than parsed.
+///
+/// # Number precision
+///
+/// Deserializing into [`serde_json::Value`] keeps integers intact up to
+/// 64 bits; anything larger — and high-precision decimals — rounds
+/// through `f64`. APIs returning wider IDs or scientific data need the
+/// `arbitrary-precision` cargo feature, which forwards to
+/// `serde_json/arbitrary_precision` and stores such numbers losslessly.
+/// The [`Json::arbitrary_precision`] constructor only exists under that
+/// feature, so code built through it cannot silently lose precision.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "arbitrary-precision")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary-precision")))]
+impl Json<serde_json::Value> {
+    /// Wraps a raw JSON value under guaranteed full-precision parsing.
+    ///
+    /// Compiles only with the `arbitrary-precision` feature enabled;
+    /// using it where the extractor output is assembled by hand (tests,
+    /// fixtures) turns a missing feature flag into a compile error
+    /// instead of silently rounded numbers.
+    pub fn arbitrary_precision(value: serde_json::Value) -> Self {
+        Self(value)
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Json<T>
+where
+    C: Client,
+    S: Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Json: {error}")))?;
+
+        let value = serde_json::from_slice(&body.into_bytes())
+            .map_err(|error| Rejection::new(format!("Json: {error}")))?;
+        Ok(Json(value))
+    }
+}
+
+/// Extractor deserializing the response body as JSON, requiring the
+/// response to be labeled `application/json`.
+///
+/// The strict counterpart of [`Json`]: rejecting mislabeled bodies makes
+/// a misconfigured endpoint visible instead of silently parsed, at the
+/// cost of dropping APIs that serve JSON as `text/plain`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictJson<T>(pub T);
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for StrictJson<T>
+where
+    C: Client,
+    S: Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = cx
+            .resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("Json: {error}")))?
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if !content_type.starts_with(mime::APPLICATION_JSON.essence_str()) {
+            return Err(Rejection::new(format!(
+                "Json: unexpected content type `{content_type}`"
+            )));
+        }
+
+        let Json(value) = Json::from_context(cx, state).await?;
+        Ok(StrictJson(value))
+    }
+}
+
+/// Extractor streaming the response body as newline-delimited JSON.
+///
+/// Yields one deserialized record per line via its [`Stream`]
+/// implementation, parsing lazily so large record-oriented responses do
+/// not pay a single up-front deserialization. Blank lines are skipped.
+///
+/// Note that response bodies are currently buffered by the backend, so
+/// the response is fully downloaded before the first record is yielded.
+#[derive(Debug)]
+pub struct Ndjson<T> {
+    bytes: Bytes,
+    offset: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Stream for Ndjson<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while this.offset < this.bytes.len() {
+            let rest = &this.bytes[this.offset..];
+            let end = rest
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .unwrap_or(rest.len());
+            let line = &rest[..end];
+            this.offset += end + 1;
+
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+
+            let record = serde_json::from_slice(line)
+                .map_err(|error| Error::new(ErrorKind::Context, format!("Ndjson: {error}")));
+            return Poll::Ready(Some(record));
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Ndjson<T>
+where
+    C: Client,
+    S: Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Ndjson: {error}")))?;
+
+        Ok(Ndjson {
+            bytes: body.into_bytes(),
+            offset: 0,
+            _marker: PhantomData,
+        })
+    }
+}
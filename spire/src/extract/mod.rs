@@ -0,0 +1,30 @@
+//! Extractors pulling typed values out of a [`Context`].
+//!
+//! Re-exports the core extractors ([`State`], [`Rejection`], the
+//! [`FromContext`] trait) and adds body-consuming extractors for common
+//! response formats: [`Json`], [`Text`], [`Html`] and the
+//! [`Select`]-driven [`Elements`] / [`Selected`].
+//!
+//! Body-consuming extractors take the response body on first use; at most
+//! one of them can appear per handler.
+//!
+//! [`Context`]: spire_core::context::Context
+
+pub use spire_core::extract::{Datasets, Extension, FromContext, FromRef, HostState, Rejection, State};
+
+mod download;
+mod json;
+mod select;
+mod text;
+#[cfg(feature = "webdriver")]
+mod view;
+
+pub use download::Download;
+pub use json::{Json, Ndjson, StrictJson};
+pub use select::{Elements, Select, SelectError, Selected};
+#[doc(hidden)]
+pub use select::{element_attrs, resolve_url};
+pub use text::{Html, Text, Title};
+#[cfg(feature = "webdriver")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webdriver")))]
+pub use view::ViewElements;
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use async_trait::async_trait;
+use scraper::ElementRef;
+use url::Url;
+
+use spire_core::backend::Client;
+use spire_core::context::Context;
+use spire_core::extract::{FromContext, Rejection};
+
+/// Declarative extraction of a type from an HTML element.
+///
+/// Usually implemented through `#[derive(Select)]` from the `macros`
+/// feature rather than by hand. The optional [`Select::selector`] scopes
+/// the type to a repeated element; the [`Elements`] and [`Selected`]
+/// extractors iterate its matches.
+pub trait Select: Sized {
+    /// CSS selector scoping this type within a document, if any.
+    fn selector() -> Option<&'static str> {
+        None
+    }
+
+    /// Extracts an instance from the given element.
+    fn select(element: &ElementRef<'_>) -> Result<Self, SelectError>;
+
+    /// Extracts an instance, resolving `absolute` URL fields against the
+    /// given base.
+    ///
+    /// The [`Elements`] and [`Selected`] extractors pass the URL of the
+    /// current request as the base, so `#[select(..., absolute)]` fields
+    /// come back as absolute URLs. The default implementation ignores the
+    /// base; `#[derive(Select)]` overrides it.
+    fn select_with_base(
+        element: &ElementRef<'_>,
+        base: Option<&Url>,
+    ) -> Result<Self, SelectError> {
+        let _ = base;
+        Self::select(element)
+    }
+}
+
+/// Failure while extracting a [`Select`] type.
+#[derive(Debug, Clone)]
+pub struct SelectError {
+    message: String,
+}
+
+impl SelectError {
+    /// Creates an error with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Error for a CSS selector that failed to parse.
+    pub fn selector(css: &str) -> Self {
+        Self::new(format!("invalid selector `{css}`"))
+    }
+
+    /// Error for a required field without a matching element.
+    pub fn missing(field: &str, css: &str) -> Self {
+        Self::new(format!("no match for field `{field}` (selector `{css}`)"))
+    }
+
+    /// Error for a field value that failed to parse into its type.
+    pub fn parse(field: &str, value: &str) -> Self {
+        Self::new(format!("cannot parse `{value}` for field `{field}`"))
+    }
+}
+
+impl fmt::Display for SelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for SelectError {}
+
+/// Resolves a possibly relative URL against the given base.
+///
+/// Handles root-relative (`/path`), protocol-relative (`//host/path`)
+/// and plain relative forms; values that do not join cleanly pass
+/// through unchanged. Used by `#[derive(Select)]` for fields marked
+/// `absolute`.
+#[doc(hidden)]
+pub fn resolve_url(base: Option<&Url>, raw: String) -> String {
+    let Some(base) = base else {
+        return raw;
+    };
+
+    match base.join(&raw) {
+        Ok(url) => url.into(),
+        Err(_) => raw,
+    }
+}
+
+/// Collects every attribute of an element into a map.
+///
+/// Used by `#[derive(Select)]` for fields marked `attrs`, which capture
+/// pages encoding data in arbitrary (e.g. `data-*`) attributes. The
+/// iteration order of the returned map is unspecified.
+#[doc(hidden)]
+pub fn element_attrs(element: &ElementRef<'_>) -> HashMap<String, String> {
+    element
+        .value()
+        .attrs()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect()
+}
+
+/// Extractor yielding every match of `T` in the response document.
+///
+/// Without a [`Select::selector`] on `T` the document root is extracted
+/// once, yielding zero or one element.
+#[derive(Debug, Clone, Default)]
+pub struct Elements<T>(pub Vec<T>);
+
+/// Extractor yielding the first match of `T` in the response document.
+///
+/// Rejects when the document contains no match.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Selected<T>(pub T);
+
+/// Extracts every match of `T` from an HTML document.
+pub(crate) fn select_all<T: Select>(
+    document: &str,
+    base: Option<&Url>,
+) -> Result<Vec<T>, SelectError> {
+    let html = scraper::Html::parse_document(document);
+    match T::selector() {
+        Some(css) => {
+            let selector =
+                scraper::Selector::parse(css).map_err(|_| SelectError::selector(css))?;
+            html.select(&selector)
+                .map(|element| T::select_with_base(&element, base))
+                .collect()
+        }
+        None => Ok(vec![T::select_with_base(&html.root_element(), base)?]),
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Elements<T>
+where
+    C: Client,
+    S: Sync,
+    T: Select + Send,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Elements: {error}")))?;
+        let document = String::from_utf8_lossy(&body.into_bytes()).into_owned();
+
+        let base = Url::parse(&cx.uri().to_string()).ok();
+        let items = select_all(&document, base.as_ref())
+            .map_err(|error| Rejection::new(format!("Elements: {error}")))?;
+        Ok(Elements(items))
+    }
+}
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for Selected<T>
+where
+    C: Client,
+    S: Sync,
+    T: Select + Send,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Selected: {error}")))?;
+        let document = String::from_utf8_lossy(&body.into_bytes()).into_owned();
+
+        let base = Url::parse(&cx.uri().to_string()).ok();
+        let items: Vec<T> = select_all(&document, base.as_ref())
+            .map_err(|error| Rejection::new(format!("Selected: {error}")))?;
+        items
+            .into_iter()
+            .next()
+            .map(Selected)
+            .ok_or_else(|| Rejection::new("Selected: no matching element"))
+    }
+}
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+
+use spire_core::backend::Client;
+use spire_core::context::{Context, PageText, PageTitle};
+use spire_core::extract::{FromContext, Rejection};
+
+/// Extractor yielding the textual content of the response.
+///
+/// When the backend attached a [`PageText`] extension (the browser backend
+/// does so when extracting both HTML and text), that pre-extracted text is
+/// returned without consuming the body. Otherwise the body is read as a
+/// UTF-8 string, replacing invalid sequences rather than rejecting.
+#[derive(Debug, Clone, Default)]
+pub struct Text(pub String);
+
+impl Text {
+    /// Returns only the visible text of the content, treated as HTML.
+    ///
+    /// Drops `<script>`, `<style>`, `<noscript>` and `<template>`
+    /// contents along with comments, and collapses runs of whitespace
+    /// into single spaces — clean input for indexing or NLP. The raw
+    /// content stays available in `self.0`; content that is not HTML
+    /// comes back with only its whitespace collapsed.
+    pub fn visible(&self) -> String {
+        visible_text(&self.0)
+    }
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Text
+where
+    C: Client,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let response = cx
+            .resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("Text: {error}")))?;
+        if let Some(text) = response.extensions().get::<PageText>() {
+            return Ok(Text(text.0.clone()));
+        }
+
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Text: {error}")))?;
+        Ok(Text(String::from_utf8_lossy(&body.into_bytes()).into_owned()))
+    }
+}
+
+/// Extractor yielding the document title attached by the backend.
+///
+/// Rejects when the backend did not provide a [`PageTitle`] extension;
+/// only rendering backends such as the browser backend do.
+#[derive(Debug, Clone, Default)]
+pub struct Title(pub String);
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Title
+where
+    C: Client,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let response = cx
+            .resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("Title: {error}")))?;
+        match response.extensions().get::<PageTitle>() {
+            Some(title) => Ok(Title(title.0.clone())),
+            None => Err(Rejection::new("Title: not provided by the backend")),
+        }
+    }
+}
+
+/// Extractor yielding the raw HTML of the response body.
+///
+/// The document is kept as a string; parse it on demand with
+/// [`Html::parse`]. The parsed [`scraper::Html`] is not `Send`, so keep it
+/// inside a synchronous scope and do not hold it across `.await` points.
+#[derive(Debug, Clone, Default)]
+pub struct Html(pub String);
+
+impl Html {
+    /// Parses the document for ad-hoc selector queries.
+    pub fn parse(&self) -> scraper::Html {
+        scraper::Html::parse_document(&self.0)
+    }
+
+    /// Returns only the visible text of the document.
+    ///
+    /// See [`Text::visible`] for what is dropped and how whitespace is
+    /// handled.
+    pub fn text_content(&self) -> String {
+        visible_text(&self.0)
+    }
+}
+
+/// Elements whose text never renders; their subtrees are skipped.
+const INVISIBLE_ELEMENTS: [&str; 4] = ["script", "style", "noscript", "template"];
+
+/// Concatenates the visible text nodes of an HTML document, collapsing
+/// whitespace. Comments are not text nodes and fall away on their own.
+fn visible_text(html: &str) -> String {
+    let document = scraper::Html::parse_document(html);
+    let mut stack = vec![document.tree.root()];
+    let mut words: Vec<&str> = Vec::new();
+
+    while let Some(node) = stack.pop() {
+        match node.value() {
+            scraper::Node::Text(text) => words.extend(text.split_whitespace()),
+            scraper::Node::Element(element)
+                if INVISIBLE_ELEMENTS.contains(&element.name()) => continue,
+            _ => {}
+        }
+
+        // Reversed so the leftmost child is popped first, keeping
+        // document order.
+        let children: Vec<_> = node.children().collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    words.join(" ")
+}
+
+#[async_trait]
+impl<C, S> FromContext<C, S> for Html
+where
+    C: Client,
+    S: Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        let body = cx
+            .body()
+            .await
+            .map_err(|error| Rejection::new(format!("Html: {error}")))?;
+        Ok(Html(String::from_utf8_lossy(&body.into_bytes()).into_owned()))
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+
+use spire_core::backend::Client;
+use spire_core::context::Context;
+use spire_core::extract::{FromContext, Rejection};
+use spire_webdriver::BrowserClientMarker;
+
+use crate::extract::select::{select_all, Select};
+
+/// Extractor yielding every match of `T` in the live browser DOM.
+///
+/// The browser-backend counterpart of [`Elements`]: every element matching
+/// [`Select::selector`] is snapshotted from the session via its outer HTML
+/// and `T` is populated from the snapshot exactly as it would be from a
+/// fetched document. Elements disappearing between lookup and snapshot are
+/// skipped, so optional fields of `T` behave the same as on static HTML.
+///
+/// Without a [`Select::selector`] on `T` the serialized page source is
+/// extracted once, yielding zero or one element.
+///
+/// [`Elements`]: crate::extract::Elements
+#[derive(Debug, Clone, Default)]
+pub struct ViewElements<T>(pub Vec<T>);
+
+#[async_trait]
+impl<C, S, T> FromContext<C, S> for ViewElements<T>
+where
+    C: Client + BrowserClientMarker,
+    S: Sync,
+    T: Select + Send,
+{
+    type Rejection = Rejection;
+
+    async fn from_context(cx: &mut Context<C>, _state: &S) -> Result<Self, Self::Rejection> {
+        // Make sure the session has navigated before touching the DOM.
+        cx.resolve()
+            .await
+            .map_err(|error| Rejection::new(format!("ViewElements: {error}")))?;
+        let view = cx.client().view();
+        let base = url::Url::parse(&cx.uri().to_string()).ok();
+
+        let items = match T::selector() {
+            Some(css) => {
+                let snapshots = view
+                    .find_all_outer_html(css)
+                    .await
+                    .map_err(|error| Rejection::new(format!("ViewElements: {error}")))?;
+
+                let mut items = Vec::with_capacity(snapshots.len());
+                for snapshot in snapshots {
+                    // The snapshot root is the matched element itself; take
+                    // only the first match so nested matches (returned as
+                    // their own snapshots) are not counted twice.
+                    let item = select_all::<T>(&snapshot, base.as_ref())
+                        .map_err(|error| Rejection::new(format!("ViewElements: {error}")))?
+                        .into_iter()
+                        .next();
+                    items.extend(item);
+                }
+
+                items
+            }
+            None => {
+                let source = view
+                    .source()
+                    .await
+                    .map_err(|error| Rejection::new(format!("ViewElements: {error}")))?;
+                select_all(&source, base.as_ref())
+                    .map_err(|error| Rejection::new(format!("ViewElements: {error}")))?
+            }
+        };
+
+        Ok(ViewElements(items))
+    }
+}
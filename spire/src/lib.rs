@@ -0,0 +1,94 @@
+//! The flexible crawler and scraper framework powered by [`tokio`] and
+//! [`tower`].
+//!
+//! Spire structures a crawl around three pieces:
+//!
+//! - a [`backend`] resolving requests — plain HTTP ([`HttpClient`]) or a
+//!   real browser ([`BrowserBackend`]);
+//! - a [`Router`] dispatching responses to async handlers by [`Tag`];
+//! - [`dataset`]s storing the request queue and the scraped records.
+//!
+//! Handlers are plain async functions; their arguments are [`extract`]ors
+//! pulling typed values out of the per-request [`Context`].
+//!
+//! ```no_run
+//! use spire::extract::{Elements, Select};
+//! use spire::{Client, Router};
+//! use spire_core::context::RequestQueue;
+//!
+//! #[derive(Debug, spire::Select)]
+//! #[select(css = ".quote")]
+//! struct Quote {
+//!     #[select(css = ".text")]
+//!     text: String,
+//! }
+//!
+//! async fn quotes(queue: RequestQueue, quotes: Elements<Quote>) {
+//!     for quote in quotes.0 {
+//!         println!("{}", quote.text);
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let backend = spire::backend::HttpClient::new();
+//!     let router = Router::new().route("quotes", quotes);
+//!     let client = Client::new(backend, router).with_initial_request(
+//!         "quotes",
+//!         http::Request::get("https://quotes.toscrape.com/").body(()).unwrap(),
+//!     );
+//!     client.run().await.unwrap();
+//! }
+//! ```
+//!
+//! [`tokio`]: https://docs.rs/tokio
+//! [`tower`]: https://docs.rs/tower
+//! [`HttpClient`]: crate::backend::HttpClient
+//! [`BrowserBackend`]: crate::backend::BrowserBackend
+//! [`Tag`]: crate::context::Tag
+//! [`Context`]: crate::context::Context
+
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub use spire_core::{
+    client, context, dataset, handler, routing, BoxError, Client, Error, ErrorKind, IntoSignal,
+    Result, Router, Signal,
+};
+
+pub mod extract;
+
+pub use http;
+
+/// Backend implementations resolving requests into responses.
+pub mod backend {
+    pub use spire_core::backend::*;
+
+    #[cfg(feature = "reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+    pub use spire_reqwest::{HttpClient, HttpClientBuilder};
+
+    #[cfg(feature = "webdriver")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "webdriver")))]
+    pub use spire_webdriver::{
+        Browser, BrowserBackend, BrowserBuilder, BrowserClient, BrowserClientMarker,
+        BrowserError, BrowserPool, BrowserResult, ClientConfig, PoolConfig, View,
+        WebDriverConfig,
+    };
+}
+
+/// Tower middleware shipped with the framework.
+pub mod layer {
+    pub use spire_core::layer::*;
+}
+
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use spire_macros::Select;
+
+// Implementation detail of the `Select` derive; not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use scraper::{ElementRef, Html, Selector};
+    pub use url::Url;
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "macros")]
+
+use spire::__private::Html;
+use spire::extract::Select;
+
+#[derive(Debug, spire::Select)]
+struct Product {
+    #[select(css = ".name")]
+    name: String,
+    #[select(css = ".price")]
+    price: Option<String>,
+}
+
+#[derive(Debug, spire::Select)]
+struct Listing {
+    #[select(css = "h1")]
+    title: String,
+    #[select(css = ".product")]
+    products: Vec<Product>,
+}
+
+#[test]
+fn nested_vec_extracts_each_match() {
+    let html = Html::parse_document(
+        r#"<html><body>
+            <h1>Catalog</h1>
+            <div class="product"><span class="name">Anvil</span><span class="price">$10</span></div>
+            <div class="product"><span class="name">Rope</span></div>
+        </body></html>"#,
+    );
+
+    let listing = Listing::select(&html.root_element()).unwrap();
+    assert_eq!(listing.title, "Catalog");
+    assert_eq!(listing.products.len(), 2);
+    assert_eq!(listing.products[0].name, "Anvil");
+    assert_eq!(listing.products[0].price.as_deref(), Some("$10"));
+    assert_eq!(listing.products[1].name, "Rope");
+    assert_eq!(listing.products[1].price, None);
+}
+
+#[derive(Debug, spire::Select)]
+struct Snippet {
+    #[select(css = ".author", default = "N/A")]
+    author: String,
+    #[select(css = ".license", default = "unknown")]
+    license: Option<String>,
+}
+
+#[test]
+fn default_fills_missing_fields() {
+    let html = Html::parse_document("<html><body><p>no metadata here</p></body></html>");
+
+    let snippet = Snippet::select(&html.root_element()).unwrap();
+    assert_eq!(snippet.author, "N/A");
+    assert_eq!(snippet.license.as_deref(), Some("unknown"));
+}
+
+#[test]
+fn default_is_ignored_when_the_field_matches() {
+    let html = Html::parse_document(
+        r#"<html><body><span class="author">Ada</span></body></html>"#,
+    );
+
+    let snippet = Snippet::select(&html.root_element()).unwrap();
+    assert_eq!(snippet.author, "Ada");
+}
+
+#[derive(Debug, spire::Select)]
+struct Offer {
+    #[select(css = ".price", parse, trim, strip = "$")]
+    price: f64,
+    #[select(css = ".stock", parse)]
+    stock: Option<i64>,
+    #[select(css = ".rating", parse, default = "0")]
+    rating: u32,
+}
+
+#[test]
+fn parse_converts_cleaned_text_into_typed_fields() {
+    let html = Html::parse_document(
+        r#"<html><body>
+            <span class="price"> $19.99 </span>
+            <span class="stock">42</span>
+        </body></html>"#,
+    );
+
+    let offer = Offer::select(&html.root_element()).unwrap();
+    assert_eq!(offer.price, 19.99);
+    assert_eq!(offer.stock, Some(42));
+    assert_eq!(offer.rating, 0);
+}
+
+#[test]
+fn parse_rejects_malformed_values() {
+    let html = Html::parse_document(
+        r#"<html><body><span class="price">free</span></body></html>"#,
+    );
+
+    let error = Offer::select(&html.root_element()).unwrap_err();
+    assert!(error.to_string().contains("price"));
+}
+
+#[test]
+fn nested_vec_without_matches_is_empty() {
+    let html = Html::parse_document("<html><body><h1>Catalog</h1></body></html>");
+
+    let listing = Listing::select(&html.root_element()).unwrap();
+    assert!(listing.products.is_empty());
+}
+
+#[derive(Debug, spire::Select)]
+struct Links {
+    #[select(css = "a", attr = "href", absolute)]
+    hrefs: Vec<String>,
+    #[select(css = "img", attr = "src", absolute)]
+    image: Option<String>,
+}
+
+#[test]
+fn absolute_resolves_relative_urls() {
+    let html = Html::parse_document(
+        r#"<html><body>
+            <a href="/root">root</a>
+            <a href="sibling">sibling</a>
+            <a href="//cdn.example.com/x">protocol</a>
+            <a href="https://other.example.com/full">full</a>
+            <img src="img/logo.png">
+        </body></html>"#,
+    );
+
+    let base = spire::__private::Url::parse("https://example.com/catalog/page").unwrap();
+    let links = Links::select_with_base(&html.root_element(), Some(&base)).unwrap();
+    assert_eq!(
+        links.hrefs,
+        vec![
+            "https://example.com/root",
+            "https://example.com/catalog/sibling",
+            "https://cdn.example.com/x",
+            "https://other.example.com/full",
+        ],
+    );
+    assert_eq!(links.image.as_deref(), Some("https://example.com/catalog/img/logo.png"));
+}
+
+#[test]
+fn absolute_without_base_passes_through() {
+    let html = Html::parse_document(r#"<a href="/root">root</a>"#);
+
+    let links = Links::select(&html.root_element()).unwrap();
+    assert_eq!(links.hrefs, vec!["/root"]);
+}
+
+#[derive(Debug, spire::Select)]
+struct Widget {
+    #[select(css = ".widget", attrs)]
+    data: std::collections::HashMap<String, String>,
+}
+
+#[test]
+fn attrs_capture_every_attribute() {
+    let html = Html::parse_document(
+        r#"<html><body>
+            <div class="widget" data-id="7" data-kind="gauge" title="Pressure"></div>
+        </body></html>"#,
+    );
+
+    let widget = Widget::select(&html.root_element()).unwrap();
+    assert_eq!(widget.data.get("data-id").map(String::as_str), Some("7"));
+    assert_eq!(widget.data.get("data-kind").map(String::as_str), Some("gauge"));
+    assert_eq!(widget.data.get("title").map(String::as_str), Some("Pressure"));
+    assert_eq!(widget.data.get("class").map(String::as_str), Some("widget"));
+
+    let html = Html::parse_document("<html><body></body></html>");
+    assert!(Widget::select(&html.root_element()).is_err());
+}
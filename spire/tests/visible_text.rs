@@ -0,0 +1,30 @@
+use spire::extract::{Html, Text};
+
+const PAGE: &str = r#"
+    <html>
+        <head>
+            <title>Anvils</title>
+            <style>body { color: red; }</style>
+        </head>
+        <body>
+            <script>var tracker = "noise";</script>
+            <!-- a comment -->
+            <h1>Acme   Anvils</h1>
+            <p>Quality
+               drop-forged   anvils.</p>
+            <noscript>Please enable JavaScript.</noscript>
+        </body>
+    </html>
+"#;
+
+#[test]
+fn text_content_drops_scripts_styles_and_comments() {
+    let html = Html(PAGE.to_owned());
+    assert_eq!(html.text_content(), "Anvils Acme Anvils Quality drop-forged anvils.");
+}
+
+#[test]
+fn visible_collapses_whitespace() {
+    let text = Text("  plain\n\ttext  ".to_owned());
+    assert_eq!(text.visible(), "plain text");
+}